@@ -14,14 +14,113 @@
 //!
 //! Named Keys:
 //! - "workflows": Dictionary of workflow_id -> WorkflowData
-//! - "workflow_count": Total number of workflows created
+//! - "workflow_count": Highest workflow ID in use (auto-increment counter)
+//! - "total_transitions": Number of state transitions committed contract-wide
+//! - "deployed_at": Block timestamp of contract installation
+//! - "deadline_windows": Sorted Vec<u64> of deadline windows holding open deadlines
+//! - "deadline_buckets": Dictionary of window -> sorted Vec<(deadline, workflow_id)>
 //! - "transitions": Dictionary of workflow_id -> Vec<TransitionRecord>
+//! - "data_changes": Dictionary of workflow_id -> Vec<DataChangeRecord>
+//! - "workflows_archive": Dictionary of workflow_id -> archived WorkflowData
+//! - "transitions_archive": Dictionary of workflow_id -> archived Vec<TransitionRecord>
+//! - "creator_index": Dictionary of creator account hash -> Vec<workflow_id>
+//! - "template_counts": Dictionary of template_hash -> workflows created from it
+//! - "escalation_index": Dictionary of assigned approver -> Vec<workflow_id> awaiting them
+//! - "comments": Dictionary of workflow_id:index -> comment text of a transition
+//! - "comment_max_len": Maximum stored comment length in bytes
+//! - "max_history_len": Maximum number of history records per workflow
+//! - "max_escalations": Maximum number of escalations per workflow
+//! - "categories": Dictionary of category_id -> name hash (decision taxonomy)
+//! - "category_required": Whether terminal transitions must supply a category
+//! - "intake_approval": Whether new workflows start in CREATED_PENDING
+//! - "restricted_creation": Whether only REQUESTER accounts may create workflows
+//! - "paused": Emergency stop flag; blocks workflow mutations when set
+//! - "rate_limit": (calls per window, window seconds); zero calls = unlimited
+//! - "call_budget": Dictionary of account_window -> calls made in that window
+//! - "locked": Dictionary of workflow_id -> whether a transition is in progress
+//! - "admin": Account allowed to call administrative entry points
+//! - "pending_admin": Option<AccountHash> proposed to take over as admin
+//! - "roles": Dictionary of account hash -> role mask (role registry)
+//! - "delegations": Dictionary of delegator -> (delegate, expires_at, role_mask)
+//! - "delegates": Dictionary of delegate -> Vec<AccountHash> delegators
+//! - "transition_rules": Dictionary of template_hash -> Vec<TransitionRule>
+//! - "self_approval": Dictionary of template_hash -> whether creators may approve
+//! - "comment_required": Dictionary of template_hash -> Vec<u8> states needing a comment
+//! - "rule_conditions": Dictionary of template_hash -> Vec<RuleCondition> expected conditions
+//! - "template_approvers": Dictionary of template_hash -> Vec<AccountHash> approver pool
+//! - "seen_requests": Dictionary of hash(workflow_id, request_id) -> bool
+//! - "time_in_state": Dictionary of state -> (total dwell time, samples)
+//! - "state_slas": Dictionary of template_hash_state -> SLA seconds
+//! - "terminal_states": Dictionary of template_hash -> Vec<u8> terminal states
+//! - "terminal_grace": Dictionary of template_hash -> grace seconds before finalization
+//! - "min_transition_intervals": Dictionary of template_hash -> seconds between transitions
+//! - "pending_approvals": Dictionary of workflow_id -> Vec<AccountHash>
+//! - "state_catalog": Dictionary of custom state -> (name hash, is_terminal)
+//! - "state_counts": Dictionary of state -> number of workflows currently in it
+//! - "transition_type_counts": Dictionary of to_state -> transitions ever committed into it
+//! - "state_index": Dictionary of state -> Vec<workflow_id> currently in it
+//! - "audit_seq": Sequence number of the last audit stream entry
+//! - "audit_stream": Dictionary of seq -> (audit kind, reference)
+//! - "role_changes": Dictionary of seq -> (account, role_mask, (actor, timestamp))
+//! - "metadata": Dictionary of workflow_id:attr_name -> attachment hash
+//! - "metadata_changes": Dictionary of seq -> (workflow_id, (attr hash, value), (actor, timestamp))
+//! - "creator_changes": Dictionary of seq -> (workflow_id, (old, new creator), (actor, timestamp))
+//! - "original_creators": Dictionary of workflow_id -> creator at creation, once reassigned
+//! - "history_purges": Dictionary of workflow_id -> (records purged, hash of last purged record)
+//! - "purge_log": Dictionary of seq -> (workflow_id, (cutoff, records purged), (actor, timestamp))
+//! - "global_leaves": Dictionary of workflow_id -> audit digest last swept
+//! - "global_nodes": Dictionary of "level:index" -> internal node of the global Merkle tree
+//! - "global_tree_size": u64 number of workflow IDs (from 1) covered by the global tree
+//! - "global_root": Merkle root over all swept audit digests
+//! - "events_topic": Option<String> message topic of events; None when disabled
+//! - "workflow_contract_package_hash": Hash of the contract package holding all versions
+//!
+//! # Events
+//!
+//! Events are emitted as Casper 2.0 contract messages on the
+//! "workflow_events" topic, or the topic named at install by `events_topic`.
+//! Installing with `enable_events = false` registers no topic and emits
+//! nothing. Each payload is a string of the form
+//! `<event>;<field>=<value>;...`, with fields in a fixed order per event.
+//! Account hashes are lowercase hex; numbers are decimal. New fields are
+//! only ever appended, so indexers may ignore unknown trailing fields.
+//!
+//! - `workflow_created;workflow_id=<U256>;template_hash=<hex>;creator=<hex>;created_at=<u64>`
+//!   (emitted before the `transition` marking the creation)
+//! - `transition;workflow_id=<U256>;from_state=<u8>;to_state=<u8>;actor=<hex>;timestamp=<u64>`
+//!   (emitted on creation with from_state = to_state = the initial state)
+//! - `workflow_completed;workflow_id=<U256>;final_state=<u8>;timestamp=<u64>`
+//!   (emitted after the `transition` that moves a workflow into a terminal state, or
+//!   by `finalize` for templates with a terminal grace period)
+//! - `data_update;workflow_id=<U256>;data_hash=<hex>;actor=<hex>;timestamp=<u64>`
+//! - `admin_override;workflow_id=<U256>;from_state=<u8>;to_state=<u8>;actor=<hex>;timestamp=<u64>`
+//!   (emitted after the `transition` of a manual state correction)
+//! - `metadata;workflow_id=<U256>;attr=<str>;value=<hex>;actor=<hex>;timestamp=<u64>`
+//! - `reassign;workflow_id=<U256>;old_creator=<hex>;new_creator=<hex>;actor=<hex>;timestamp=<u64>`
+//! - `auto_escalate;workflow_id=<U256>;deadline=<u64>;actor=<hex>;timestamp=<u64>`
+//!   (emitted after the `transition` of an SLA-triggered escalation)
+//! - `void;workflow_id=<U256>;actor=<hex>;timestamp=<u64>`
+//! - `archive;workflow_id=<U256>;actor=<hex>;timestamp=<u64>`
+//! - `heartbeat;timestamp=<u64>;workflow_count=<U256>;contract_version=<str>`
 //!
 //! # Security Model
 //!
 //! - All state changes require caller signature verification
-//! - Role-based permissions enforced via role_mask parameter
-//! - No administrative backdoors or privileged accounts
+//! - Role-based permissions enforced against the on-chain role registry
+//! - A single admin account (initially the installer) gates administrative
+//!   entry points; it is handed over in two steps (propose, then accept)
+//! - The admin also holds the ADMIN role, which grants read access to
+//!   confidential records alongside AUDITOR
+//! - Confidentiality only governs what entry points return; contract
+//!   dictionaries are readable by anyone querying global state, so
+//!   confidential records cannot carry on-chain comment text
+//! - Block time is assumed non-decreasing; a mutation whose block time
+//!   precedes the workflow's last update reverts, keeping histories monotonic
+//! - Installing with `gate_mutations = true` restricts every non-admin
+//!   entry point that changes workflow records to the "mutators" access
+//!   group at the Casper level, on top of the role checks (see `call`)
+//! - Workflows are locked while a transition is validated and committed, so
+//!   a nested call mutating the same workflow reverts with `WorkflowLocked`
 //! - Contract upgrade requires separate deployment (no in-place upgrade)
 //!
 //! # Reference
@@ -29,12 +128,19 @@
 //! Casper Smart Contract Documentation:
 //! https://docs.casper.network/developers/writing-onchain-code/
 
-#![no_std]
-#![no_main]
+#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(test), no_main)]
 
 extern crate alloc;
 
-use alloc::{string::ToString, vec, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 
 use casper_contract::{
     contract_api::{runtime, storage},
@@ -44,10 +150,12 @@ use casper_types::{
     account::AccountHash,
     bytesrepr::{self, FromBytes, ToBytes},
     ApiError, CLType, CLTyped, CLValue, 
-    EntryPointAccess, EntryPointType, EntryPoints, Parameter,
+    EntryPointAccess, EntryPointType, EntryPoints, Group, Parameter,
     Key, URef, U256,
 };
-use casper_types::contracts::{EntryPoint, NamedKeys};
+use casper_types::contract_messages::{MessagePayload, MessageTopicOperation};
+use casper_types::contracts::{ContractPackageHash, EntryPoint, NamedKeys};
+use tiny_keccak::{Hasher, Keccak};
 
 // =============================================================================
 // Error Codes
@@ -55,6 +163,7 @@ use casper_types::contracts::{EntryPoint, NamedKeys};
 
 /// Custom error codes for the workflow contract.
 /// These map to Casper's ApiError::User(code) pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u16)]
 pub enum WorkflowError {
     /// Workflow with given ID does not exist
@@ -81,6 +190,40 @@ pub enum WorkflowError {
     ComplianceProofAlreadyExists = 11,
     /// Workflow not in approved state
     WorkflowNotApproved = 12,
+    /// Caller has already approved this workflow
+    DuplicateApproval = 13,
+    /// Contract is paused; workflow mutations are disabled
+    ContractPaused = 14,
+    /// Caller has exhausted its call budget for the current window
+    RateLimited = 15,
+    /// Workflow has reached the maximum number of history records
+    HistoryLimitExceeded = 16,
+    /// Block time is earlier than the workflow's last update
+    TimestampRegression = 17,
+    /// Workflow is already being processed by an outer call
+    WorkflowLocked = 18,
+    /// A workflow with the requested ID already exists
+    WorkflowAlreadyExists = 19,
+    /// The workflow's creator cannot approve it
+    SelfApprovalForbidden = 20,
+    /// Workflow has been voided and no longer accepts changes
+    WorkflowVoided = 21,
+    /// Workflow has been escalated the maximum number of times
+    MaxEscalationReached = 22,
+    /// A value could not be encoded for storage, hashing or return
+    SerializationError = 23,
+    /// Requested position is past the end of a workflow's history
+    IndexOutOfBounds = 24,
+    /// Workflow has no deadline or its deadline has not passed
+    NotOverdue = 25,
+    /// Transition requires a justification but comment_hash is all zeros
+    CommentRequired = 26,
+    /// Workflow is still within its terminal grace period
+    GracePeriodActive = 27,
+    /// Transition follows the workflow's last update too closely
+    TransitionTooSoon = 28,
+    /// Transition rule requires a condition hash that was not supplied
+    ConditionNotMet = 29,
 }
 
 impl From<WorkflowError> for ApiError {
@@ -99,27 +242,231 @@ const WORKFLOWS_DICT: &str = "workflows";
 /// Dictionary name for storing transition history
 const TRANSITIONS_DICT: &str = "transitions";
 
+/// Dictionary name for storing data hash changes
+const DATA_CHANGES_DICT: &str = "data_changes";
+
+/// Dictionary name for archived workflow data
+const WORKFLOWS_ARCHIVE_DICT: &str = "workflows_archive";
+
+/// Dictionary name for archived transition histories
+const TRANSITIONS_ARCHIVE_DICT: &str = "transitions_archive";
+
 /// Dictionary name for storing compliance proofs
 const COMPLIANCE_PROOFS_DICT: &str = "compliance_proofs";
 
+/// Dictionary name for the creator -> workflow IDs index
+const CREATOR_INDEX_DICT: &str = "creator_index";
+
+/// Dictionary name for the number of workflows created per template
+const TEMPLATE_COUNTS_DICT: &str = "template_counts";
+
+/// Dictionary name for the assigned approver -> escalated workflow IDs index
+const ESCALATION_INDEX_DICT: &str = "escalation_index";
+
+/// Dictionary name for on-chain transition comment text
+const COMMENTS_DICT: &str = "comments";
+
+/// Named key for the maximum stored comment length
+const COMMENT_MAX_LEN_KEY: &str = "comment_max_len";
+
+/// Default maximum stored comment length in bytes
+const DEFAULT_COMMENT_MAX_LEN: u32 = 1024;
+
+/// Named key for the maximum number of history records per workflow
+const MAX_HISTORY_LEN_KEY: &str = "max_history_len";
+
+/// Default maximum number of history records per workflow
+const DEFAULT_MAX_HISTORY_LEN: u32 = 1000;
+
+/// Named key for the maximum number of escalations per workflow
+const MAX_ESCALATIONS_KEY: &str = "max_escalations";
+
+/// Default maximum number of escalations per workflow
+const DEFAULT_MAX_ESCALATIONS: u8 = 1;
+
 /// Named key for workflow counter
 const WORKFLOW_COUNT_KEY: &str = "workflow_count";
 
+/// Named key for the contract-wide transition counter
+const TOTAL_TRANSITIONS_KEY: &str = "total_transitions";
+
+/// Dictionary name for the decision category taxonomy
+const CATEGORIES_DICT: &str = "categories";
+
+/// Dictionary name for the role registry
+const ROLES_DICT: &str = "roles";
+
+/// Named key for the admin account
+const ADMIN_KEY: &str = "admin";
+
+/// Named key for the proposed successor admin
+const PENDING_ADMIN_KEY: &str = "pending_admin";
+
+/// Dictionary name for delegations keyed by delegator
+const DELEGATIONS_DICT: &str = "delegations";
+
+/// Dictionary name for the delegate -> delegators reverse index
+const DELEGATES_DICT: &str = "delegates";
+
+/// Dictionary name for per-template transition rules
+const TRANSITION_RULES_DICT: &str = "transition_rules";
+
+/// Dictionary name for per-template self-approval opt-outs
+const SELF_APPROVAL_DICT: &str = "self_approval";
+
+/// Dictionary name for per-template states that require a comment
+const COMMENT_REQUIRED_DICT: &str = "comment_required";
+
+/// Dictionary name for per-template conditions gating transition rules
+const RULE_CONDITIONS_DICT: &str = "rule_conditions";
+
+/// Dictionary name for per-template approver allowlists
+const TEMPLATE_APPROVERS_DICT: &str = "template_approvers";
+
+/// Dictionary name for processed transition request IDs
+const SEEN_REQUESTS_DICT: &str = "seen_requests";
+
+/// Dictionary name for aggregate dwell time per state
+const TIME_IN_STATE_DICT: &str = "time_in_state";
+
+/// Dictionary name for per-template, per-state SLA durations
+const STATE_SLAS_DICT: &str = "state_slas";
+
+/// Dictionary name for per-template terminal state sets
+const TERMINAL_STATES_DICT: &str = "terminal_states";
+
+/// Dictionary name for per-template grace periods before finalization
+const TERMINAL_GRACE_DICT: &str = "terminal_grace";
+
+/// Dictionary name for per-template minimum intervals between transitions
+const MIN_TRANSITION_INTERVALS_DICT: &str = "min_transition_intervals";
+
+/// Dictionary name for approvals collected towards a quorum
+const PENDING_APPROVALS_DICT: &str = "pending_approvals";
+
+/// Dictionary name for the custom state catalog
+const STATE_CATALOG_DICT: &str = "state_catalog";
+
+/// Dictionary name for the number of workflows in each state
+const STATE_COUNTS_DICT: &str = "state_counts";
+
+/// Dictionary name for the number of transitions committed into each state
+const TRANSITION_TYPE_COUNTS_DICT: &str = "transition_type_counts";
+
+/// Dictionary name for the workflows in each state
+const STATE_INDEX_DICT: &str = "state_index";
+
+/// Named key for the category-required flag
+const CATEGORY_REQUIRED_KEY: &str = "category_required";
+
+/// Named key for the installation timestamp
+const DEPLOYED_AT_KEY: &str = "deployed_at";
+
+/// Named key for the intake-approval flag
+const INTAKE_APPROVAL_KEY: &str = "intake_approval";
+
+/// Named key for the restricted-creation flag
+const RESTRICTED_CREATION_KEY: &str = "restricted_creation";
+
+/// Dictionary name for the per-workflow leaves of the global Merkle tree
+const GLOBAL_LEAVES_DICT: &str = "global_leaves";
+
+/// Dictionary name for the internal nodes of the global Merkle tree
+const GLOBAL_NODES_DICT: &str = "global_nodes";
+
+/// Named key for the number of leaves in the global Merkle tree
+const GLOBAL_TREE_SIZE_KEY: &str = "global_tree_size";
+
+/// Named key for the global Merkle root
+const GLOBAL_ROOT_KEY: &str = "global_root";
+
+/// Named key for the (budget, window seconds) rate limit configuration
+const RATE_LIMIT_KEY: &str = "rate_limit";
+
+/// Dictionary name for per-account call counts within a rate limit window
+const CALL_BUDGET_DICT: &str = "call_budget";
+
+/// Dictionary name for per-workflow processing locks
+const LOCKED_DICT: &str = "locked";
+
+/// Named key for the emergency stop flag
+const PAUSED_KEY: &str = "paused";
+
+/// Named key for the audit stream sequence counter
+const AUDIT_SEQ_KEY: &str = "audit_seq";
+
+/// Dictionary name for the unified audit stream
+const AUDIT_STREAM_DICT: &str = "audit_stream";
+
+/// Dictionary name for role assignment records
+const ROLE_CHANGES_DICT: &str = "role_changes";
+
+/// Dictionary name for named off-chain attachments of workflows
+const METADATA_DICT: &str = "metadata";
+
+/// Dictionary name for attachment update records
+const METADATA_CHANGES_DICT: &str = "metadata_changes";
+
+/// Dictionary name for creator reassignment records
+const CREATOR_CHANGES_DICT: &str = "creator_changes";
+
+/// Dictionary name for the creators of reassigned workflows at creation
+const ORIGINAL_CREATORS_DICT: &str = "original_creators";
+
+/// Dictionary name for the retention state of purged histories
+const HISTORY_PURGES_DICT: &str = "history_purges";
+
+/// Dictionary name for history purge records
+const PURGE_LOG_DICT: &str = "purge_log";
+
+/// Named key for the sorted list of windows with open deadlines
+const DEADLINE_WINDOWS_KEY: &str = "deadline_windows";
+
+/// Dictionary name for the open deadlines falling in each window
+const DEADLINE_BUCKETS_DICT: &str = "deadline_buckets";
+
+/// Width of a deadline index window (one day, in ms)
+const DEADLINE_WINDOW_MS: u64 = 86_400_000;
+
 /// Named key for contract version
 const CONTRACT_VERSION_KEY: &str = "contract_version";
 
 /// Current contract version
 const CONTRACT_VERSION: &str = "1.0.0";
 
+/// Maximum number of records returned by a single paged query
+const MAX_PAGE_SIZE: u64 = 100;
+
+/// Maximum number of transition records returned by `get_histories_batch`
+const MAX_BATCH_RECORDS: u64 = 500;
+
+/// Default message topic for workflow events
+const EVENTS_TOPIC: &str = "workflow_events";
+
+/// Named key for the configured events topic
+const EVENTS_TOPIC_KEY: &str = "events_topic";
+
+/// Named key for the contract package hash
+const PACKAGE_HASH_KEY: &str = "workflow_contract_package_hash";
+
+/// Access group allowed to call gated mutating entry points
+const MUTATION_GROUP: &str = "mutators";
+
+/// Installer named key holding its mutation group URef
+const MUTATION_ACCESS_KEY: &str = "workflow_mutation_access";
+
 // =============================================================================
 // Workflow States
 // =============================================================================
 
 /// Predefined workflow states.
-/// Custom states can be defined starting from 100.
+/// Custom states can be defined starting from 100 and must be registered
+/// in the state catalog via `register_state`.
 pub mod states {
     /// Initial draft state
     pub const DRAFT: u8 = 0;
+    /// Awaiting intake approval before entering DRAFT
+    pub const CREATED_PENDING: u8 = 5;
     /// Submitted for review
     pub const PENDING_REVIEW: u8 = 1;
     /// Approved
@@ -128,8 +475,12 @@ pub mod states {
     pub const REJECTED: u8 = 11;
     /// Escalated to higher authority
     pub const ESCALATED: u8 = 20;
+    /// Rejection formally appealed to a higher authority
+    pub const APPEALED: u8 = 21;
     /// Cancelled by requester
     pub const CANCELLED: u8 = 30;
+    /// First state ID available for custom states
+    pub const CUSTOM_BASE: u8 = 100;
 }
 
 // =============================================================================
@@ -145,18 +496,130 @@ pub mod roles {
     pub const APPROVER: u64 = 1 << 1;
     /// Can approve/reject escalated workflows
     pub const SENIOR_APPROVER: u64 = 1 << 2;
-    /// Can manage workflow definitions
+    /// Held by the admin account; may read confidential records
     pub const ADMIN: u64 = 1 << 3;
     /// Can view audit logs
     pub const AUDITOR: u64 = 1 << 4;
+    /// Can approve workflow intake (maker-checker on creation)
+    pub const REVIEWER: u64 = 1 << 5;
+    /// Not a role: set in a TransitionRecord's actor_role to mark a manual
+    /// admin override (see `admin_override_state`)
+    pub const OVERRIDE_FLAG: u64 = 1 << 63;
+    /// Not a role: set in a TransitionRecord's actor_role to mark a
+    /// system-driven SLA escalation (see `auto_escalate`)
+    pub const SYSTEM_FLAG: u64 = 1 << 62;
+}
+
+// =============================================================================
+// Audit Stream Kinds
+// =============================================================================
+
+/// Tags identifying the source of an audit stream entry.
+pub mod audit_kinds {
+    /// Workflow transition; the reference is the workflow ID and the entry
+    /// corresponds to the next record in that workflow's history
+    pub const TRANSITION: u8 = 1;
+    /// Role assignment; the reference is the entry's own sequence number,
+    /// keying the record in "role_changes"
+    pub const ROLE_CHANGE: u8 = 2;
+    /// Attachment update; the reference is the entry's own sequence number,
+    /// keying the record in "metadata_changes"
+    pub const METADATA_CHANGE: u8 = 3;
+    /// Data hash change; the reference is the workflow ID and the entry
+    /// corresponds to the next record in that workflow's data changes
+    pub const DATA_CHANGE: u8 = 4;
+    /// Creator reassignment; the reference is the entry's own sequence
+    /// number, keying the record in "creator_changes"
+    pub const CREATOR_CHANGE: u8 = 5;
+    /// History purge; the reference is the entry's own sequence number,
+    /// keying the record in "purge_log"
+    pub const HISTORY_PURGE: u8 = 6;
+}
+
+// =============================================================================
+// Hash Algorithms
+// =============================================================================
+
+/// Algorithms that may have produced a stored 32-byte digest.
+/// Verifiers recompute hashes off-chain with the recorded algorithm.
+pub mod hash_algos {
+    /// Keccak-256 (default)
+    pub const KECCAK256: u8 = 0;
+    /// Blake2b-256, as used on-chain by the contract's own hashes
+    pub const BLAKE2B: u8 = 1;
+    /// SHA-256
+    pub const SHA256: u8 = 2;
 }
 
 // =============================================================================
 // Data Structures
 // =============================================================================
 
+/// Allowed transition for a template: (from_state, to_state, required_role_mask).
+/// The caller must hold at least one role in the mask.
+pub type TransitionRule = (u8, u8, u64);
+
+/// Off-chain condition gating a template transition:
+/// (from_state, to_state, expected_condition). The transition must supply
+/// a condition_hash equal to expected_condition, e.g. a risk-check result.
+pub type RuleCondition = (u8, u8, [u8; 32]);
+
+/// Compact list-view row of a workflow:
+/// ((id, current_state), (creator, assigned_approver), (is_completed, updated_at)).
+pub type WorkflowSummary = ((U256, u8), (AccountHash, Option<AccountHash>), (bool, u64));
+
+/// Current WorkflowData schema version, written as the leading byte.
+///
+/// - v1: never written; rejected, as unversioned IDs 1-255 start with 0x01
+/// - v2: id through required_approvals (first versioned layout)
+/// - v3: adds state_entered_at
+/// - v4: adds assigned_approver
+/// - v5: adds template_seq
+/// - v6: adds is_voided
+/// - v7: adds hash_algo
+/// - v8: adds escalation_count
+/// - v9: adds genesis_hash
+/// - v10: adds finalizes_at
+const WORKFLOW_DATA_VERSION: u8 = 10;
+
+/// Byte layout of the current WorkflowData version, as `name:type` pairs
+/// in serialization order after the version byte. Types use the casper
+/// bytesrepr encoding (U256 is length-prefixed little-endian, Option is a
+/// 0/1 tag followed by the value, integers are little-endian).
+const WORKFLOW_DATA_LAYOUT: &[&str] = &[
+    "id:U256",
+    "template_hash:[u8;32]",
+    "data_hash:[u8;32]",
+    "current_state:u8",
+    "creator:AccountHash",
+    "created_at:u64",
+    "updated_at:u64",
+    "is_completed:bool",
+    "deadline:u64",
+    "required_approvals:u8",
+    "state_entered_at:u64",
+    "assigned_approver:Option<AccountHash>",
+    "template_seq:u64",
+    "is_voided:bool",
+    "hash_algo:u8",
+    "escalation_count:u8",
+    "genesis_hash:[u8;32]",
+    "finalizes_at:u64",
+];
+
 /// Workflow instance data stored on-chain.
 /// Only essential audit data is stored; business data remains off-chain.
+/// Serialized with a leading schema version byte so older records keep
+/// decoding after fields are added. Records in the original unversioned
+/// layout, which began directly with the id, do not decode: their leading
+/// byte is not a version.
+///
+/// The byte layout is a stable part of the contract interface: fields are
+/// written in declaration order, new fields are only ever appended, and
+/// `get_schema` reports the layout of every record type by version. The
+/// CLType stays `Any` because a versioned, append-only record has no
+/// faithful structured CLType, and changing it would break existing
+/// readers of stored values.
 #[derive(Clone)]
 pub struct WorkflowData {
     /// Unique workflow identifier
@@ -167,7 +630,8 @@ pub struct WorkflowData {
     pub data_hash: [u8; 32],
     /// Current state of the workflow
     pub current_state: u8,
-    /// Account that created the workflow
+    /// Account that created the workflow, or its successor after
+    /// `reassign_creator`
     pub creator: AccountHash,
     /// Block timestamp of creation
     pub created_at: u64,
@@ -175,6 +639,29 @@ pub struct WorkflowData {
     pub updated_at: u64,
     /// Whether workflow has reached terminal state
     pub is_completed: bool,
+    /// Block timestamp by which the current state should be left (0 = none)
+    pub deadline: u64,
+    /// Distinct approvals needed for PENDING_REVIEW -> APPROVED
+    pub required_approvals: u8,
+    /// Block timestamp at which the current state was entered
+    pub state_entered_at: u64,
+    /// Senior approver an ESCALATED workflow is assigned to
+    pub assigned_approver: Option<AccountHash>,
+    /// Sequence number among workflows of the same template (1-based;
+    /// 0 for records written before v5)
+    pub template_seq: u64,
+    /// Whether the workflow was voided (hidden from operation, never deleted)
+    pub is_voided: bool,
+    /// Algorithm that produced template_hash and data_hash (see `hash_algos`)
+    pub hash_algo: u8,
+    /// Number of times the workflow has entered ESCALATED
+    pub escalation_count: u8,
+    /// Commitment to the immutable creation fields (see `genesis_hash`);
+    /// zero for workflows created before v9
+    pub genesis_hash: [u8; 32],
+    /// Block time (ms) at which a terminal grace period ends; 0 when the
+    /// workflow did not enter its terminal state under a grace period
+    pub finalizes_at: u64,
 }
 
 impl CLTyped for WorkflowData {
@@ -186,6 +673,7 @@ impl CLTyped for WorkflowData {
 impl ToBytes for WorkflowData {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut result = Vec::new();
+        result.append(&mut WORKFLOW_DATA_VERSION.to_bytes()?);
         result.append(&mut self.id.to_bytes()?);
         result.append(&mut self.template_hash.to_bytes()?);
         result.append(&mut self.data_hash.to_bytes()?);
@@ -194,11 +682,22 @@ impl ToBytes for WorkflowData {
         result.append(&mut self.created_at.to_bytes()?);
         result.append(&mut self.updated_at.to_bytes()?);
         result.append(&mut self.is_completed.to_bytes()?);
+        result.append(&mut self.deadline.to_bytes()?);
+        result.append(&mut self.required_approvals.to_bytes()?);
+        result.append(&mut self.state_entered_at.to_bytes()?);
+        result.append(&mut self.assigned_approver.to_bytes()?);
+        result.append(&mut self.template_seq.to_bytes()?);
+        result.append(&mut self.is_voided.to_bytes()?);
+        result.append(&mut self.hash_algo.to_bytes()?);
+        result.append(&mut self.escalation_count.to_bytes()?);
+        result.append(&mut self.genesis_hash.to_bytes()?);
+        result.append(&mut self.finalizes_at.to_bytes()?);
         Ok(result)
     }
 
     fn serialized_length(&self) -> usize {
-        self.id.serialized_length()
+        WORKFLOW_DATA_VERSION.serialized_length()
+            + self.id.serialized_length()
             + self.template_hash.serialized_length()
             + self.data_hash.serialized_length()
             + self.current_state.serialized_length()
@@ -206,12 +705,30 @@ impl ToBytes for WorkflowData {
             + self.created_at.serialized_length()
             + self.updated_at.serialized_length()
             + self.is_completed.serialized_length()
+            + self.deadline.serialized_length()
+            + self.required_approvals.serialized_length()
+            + self.state_entered_at.serialized_length()
+            + self.assigned_approver.serialized_length()
+            + self.template_seq.serialized_length()
+            + self.is_voided.serialized_length()
+            + self.hash_algo.serialized_length()
+            + self.escalation_count.serialized_length()
+            + self.genesis_hash.serialized_length()
+            + self.finalizes_at.serialized_length()
     }
 }
 
 impl FromBytes for WorkflowData {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (id, remainder) = U256::from_bytes(bytes)?;
+        // Unversioned records begin with the id's length byte: 0x01 for IDs
+        // 1-255, so version 1 is never read, and larger IDs misread as a
+        // later version are too short for its layout
+        let (version, remainder) = u8::from_bytes(bytes)?;
+        if !(2..=WORKFLOW_DATA_VERSION).contains(&version) {
+            return Err(bytesrepr::Error::Formatting);
+        }
+
+        let (id, remainder) = U256::from_bytes(remainder)?;
         let (template_hash, remainder) = <[u8; 32]>::from_bytes(remainder)?;
         let (data_hash, remainder) = <[u8; 32]>::from_bytes(remainder)?;
         let (current_state, remainder) = u8::from_bytes(remainder)?;
@@ -219,6 +736,57 @@ impl FromBytes for WorkflowData {
         let (created_at, remainder) = u64::from_bytes(remainder)?;
         let (updated_at, remainder) = u64::from_bytes(remainder)?;
         let (is_completed, remainder) = bool::from_bytes(remainder)?;
+        let (deadline, remainder) = u64::from_bytes(remainder)?;
+        let (required_approvals, remainder) = u8::from_bytes(remainder)?;
+
+        // Before v3 the last update was always a state change
+        let (state_entered_at, remainder) = if version >= 3 {
+            u64::from_bytes(remainder)?
+        } else {
+            (updated_at, remainder)
+        };
+
+        let (assigned_approver, remainder) = if version >= 4 {
+            Option::<AccountHash>::from_bytes(remainder)?
+        } else {
+            (None, remainder)
+        };
+
+        let (template_seq, remainder) = if version >= 5 {
+            u64::from_bytes(remainder)?
+        } else {
+            (0, remainder)
+        };
+
+        let (is_voided, remainder) = if version >= 6 {
+            bool::from_bytes(remainder)?
+        } else {
+            (false, remainder)
+        };
+
+        let (hash_algo, remainder) = if version >= 7 {
+            u8::from_bytes(remainder)?
+        } else {
+            (hash_algos::KECCAK256, remainder)
+        };
+
+        let (escalation_count, remainder) = if version >= 8 {
+            u8::from_bytes(remainder)?
+        } else {
+            (0, remainder)
+        };
+
+        let (genesis_hash, remainder) = if version >= 9 {
+            <[u8; 32]>::from_bytes(remainder)?
+        } else {
+            ([0u8; 32], remainder)
+        };
+
+        let (finalizes_at, remainder) = if version >= 10 {
+            u64::from_bytes(remainder)?
+        } else {
+            (0, remainder)
+        };
 
         Ok((
             WorkflowData {
@@ -230,13 +798,52 @@ impl FromBytes for WorkflowData {
                 created_at,
                 updated_at,
                 is_completed,
+                deadline,
+                required_approvals,
+                state_entered_at,
+                assigned_approver,
+                template_seq,
+                is_voided,
+                hash_algo,
+                escalation_count,
+                genesis_hash,
+                finalizes_at,
             },
             remainder,
         ))
     }
 }
 
+/// Current TransitionRecord schema version, written as the leading byte.
+///
+/// - v1: from_state through confidential
+/// - v2: adds reason_code
+/// - v3: adds hash_algo
+/// - v4: adds block_height
+const TRANSITION_RECORD_VERSION: u8 = 4;
+
+/// Byte layout of the current TransitionRecord version (see
+/// WORKFLOW_DATA_LAYOUT for the notation).
+const TRANSITION_RECORD_LAYOUT: &[&str] = &[
+    "from_state:u8",
+    "to_state:u8",
+    "actor:AccountHash",
+    "actor_role:u64",
+    "timestamp:u64",
+    "comment_hash:[u8;32]",
+    "category_id:u32",
+    "prev_hash:[u8;32]",
+    "external_ref:[u8;32]",
+    "confidential:bool",
+    "reason_code:u16",
+    "hash_algo:u8",
+    "block_height:u64",
+];
+
 /// Record of a state transition for audit trail.
+/// Serialized with a leading schema version byte so older records keep
+/// decoding after fields are added. The layout is append-only, like
+/// WorkflowData, and is reported by `get_schema`.
 #[derive(Clone)]
 pub struct TransitionRecord {
     /// Previous state
@@ -251,6 +858,27 @@ pub struct TransitionRecord {
     pub timestamp: u64,
     /// Hash of any comments or justification (off-chain reference)
     pub comment_hash: [u8; 32],
+    /// Registered decision category (0 when none supplied)
+    pub category_id: u32,
+    /// Blake2b hash of the previous record's bytes (zero for the first),
+    /// chaining the history so any alteration is detectable
+    pub prev_hash: [u8; 32],
+    /// Approval ID in an external system of record (zero when none)
+    pub external_ref: [u8; 32],
+    /// Whether comment_hash is only disclosed to AUDITOR/ADMIN readers of
+    /// the entry points; the stored record itself is publicly readable
+    pub confidential: bool,
+    /// Categorizable reason for the decision, e.g. a rejection reason
+    /// (0 when none supplied)
+    pub reason_code: u16,
+    /// Algorithm that produced comment_hash (see `hash_algos`)
+    pub hash_algo: u8,
+    /// Height of the block that included the transaction (0 for records
+    /// written before v4). Wasm cannot observe the transaction hash, and a
+    /// transaction cannot carry its own hash as an argument, so the height
+    /// together with actor and timestamp is what locates the signed
+    /// transaction on a block explorer.
+    pub block_height: u64,
 }
 
 impl CLTyped for TransitionRecord {
@@ -262,33 +890,76 @@ impl CLTyped for TransitionRecord {
 impl ToBytes for TransitionRecord {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut result = Vec::new();
+        result.append(&mut TRANSITION_RECORD_VERSION.to_bytes()?);
         result.append(&mut self.from_state.to_bytes()?);
         result.append(&mut self.to_state.to_bytes()?);
         result.append(&mut self.actor.to_bytes()?);
         result.append(&mut self.actor_role.to_bytes()?);
         result.append(&mut self.timestamp.to_bytes()?);
         result.append(&mut self.comment_hash.to_bytes()?);
+        result.append(&mut self.category_id.to_bytes()?);
+        result.append(&mut self.prev_hash.to_bytes()?);
+        result.append(&mut self.external_ref.to_bytes()?);
+        result.append(&mut self.confidential.to_bytes()?);
+        result.append(&mut self.reason_code.to_bytes()?);
+        result.append(&mut self.hash_algo.to_bytes()?);
+        result.append(&mut self.block_height.to_bytes()?);
         Ok(result)
     }
 
     fn serialized_length(&self) -> usize {
-        self.from_state.serialized_length()
+        TRANSITION_RECORD_VERSION.serialized_length()
+            + self.from_state.serialized_length()
             + self.to_state.serialized_length()
             + self.actor.serialized_length()
             + self.actor_role.serialized_length()
             + self.timestamp.serialized_length()
             + self.comment_hash.serialized_length()
+            + self.category_id.serialized_length()
+            + self.prev_hash.serialized_length()
+            + self.external_ref.serialized_length()
+            + self.confidential.serialized_length()
+            + self.reason_code.serialized_length()
+            + self.hash_algo.serialized_length()
+            + self.block_height.serialized_length()
     }
 }
 
 impl FromBytes for TransitionRecord {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (from_state, remainder) = u8::from_bytes(bytes)?;
+        let (version, remainder) = u8::from_bytes(bytes)?;
+        if version == 0 || version > TRANSITION_RECORD_VERSION {
+            return Err(bytesrepr::Error::Formatting);
+        }
+
+        let (from_state, remainder) = u8::from_bytes(remainder)?;
         let (to_state, remainder) = u8::from_bytes(remainder)?;
         let (actor, remainder) = AccountHash::from_bytes(remainder)?;
         let (actor_role, remainder) = u64::from_bytes(remainder)?;
         let (timestamp, remainder) = u64::from_bytes(remainder)?;
         let (comment_hash, remainder) = <[u8; 32]>::from_bytes(remainder)?;
+        let (category_id, remainder) = u32::from_bytes(remainder)?;
+        let (prev_hash, remainder) = <[u8; 32]>::from_bytes(remainder)?;
+        let (external_ref, remainder) = <[u8; 32]>::from_bytes(remainder)?;
+        let (confidential, remainder) = bool::from_bytes(remainder)?;
+
+        let (reason_code, remainder) = if version >= 2 {
+            u16::from_bytes(remainder)?
+        } else {
+            (0, remainder)
+        };
+
+        let (hash_algo, remainder) = if version >= 3 {
+            u8::from_bytes(remainder)?
+        } else {
+            (hash_algos::KECCAK256, remainder)
+        };
+
+        let (block_height, remainder) = if version >= 4 {
+            u64::from_bytes(remainder)?
+        } else {
+            (0, remainder)
+        };
 
         Ok((
             TransitionRecord {
@@ -298,6 +969,99 @@ impl FromBytes for TransitionRecord {
                 actor_role,
                 timestamp,
                 comment_hash,
+                category_id,
+                prev_hash,
+                external_ref,
+                confidential,
+                reason_code,
+                hash_algo,
+                block_height,
+            },
+            remainder,
+        ))
+    }
+}
+
+/// Current DataChangeRecord schema version, written as the leading byte.
+///
+/// - v1: previous_hash through timestamp
+const DATA_CHANGE_RECORD_VERSION: u8 = 1;
+
+/// Byte layout of the current DataChangeRecord version (see
+/// WORKFLOW_DATA_LAYOUT for the notation).
+const DATA_CHANGE_RECORD_LAYOUT: &[&str] = &[
+    "previous_hash:[u8;32]",
+    "data_hash:[u8;32]",
+    "hash_algo:u8",
+    "actor:AccountHash",
+    "timestamp:u64",
+];
+
+/// Record of a change to a workflow's business data hash.
+/// Kept apart from the transition history, which stays purely about state.
+/// Serialized with a leading schema version byte like TransitionRecord.
+#[derive(Clone)]
+pub struct DataChangeRecord {
+    /// Data hash before the change
+    pub previous_hash: [u8; 32],
+    /// Data hash after the change
+    pub data_hash: [u8; 32],
+    /// Algorithm that produced both hashes (see `hash_algos`)
+    pub hash_algo: u8,
+    /// Account that made the change
+    pub actor: AccountHash,
+    /// Block timestamp of the change
+    pub timestamp: u64,
+}
+
+impl CLTyped for DataChangeRecord {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for DataChangeRecord {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = Vec::new();
+        result.append(&mut DATA_CHANGE_RECORD_VERSION.to_bytes()?);
+        result.append(&mut self.previous_hash.to_bytes()?);
+        result.append(&mut self.data_hash.to_bytes()?);
+        result.append(&mut self.hash_algo.to_bytes()?);
+        result.append(&mut self.actor.to_bytes()?);
+        result.append(&mut self.timestamp.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        DATA_CHANGE_RECORD_VERSION.serialized_length()
+            + self.previous_hash.serialized_length()
+            + self.data_hash.serialized_length()
+            + self.hash_algo.serialized_length()
+            + self.actor.serialized_length()
+            + self.timestamp.serialized_length()
+    }
+}
+
+impl FromBytes for DataChangeRecord {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (version, remainder) = u8::from_bytes(bytes)?;
+        if version == 0 || version > DATA_CHANGE_RECORD_VERSION {
+            return Err(bytesrepr::Error::Formatting);
+        }
+
+        let (previous_hash, remainder) = <[u8; 32]>::from_bytes(remainder)?;
+        let (data_hash, remainder) = <[u8; 32]>::from_bytes(remainder)?;
+        let (hash_algo, remainder) = u8::from_bytes(remainder)?;
+        let (actor, remainder) = AccountHash::from_bytes(remainder)?;
+        let (timestamp, remainder) = u64::from_bytes(remainder)?;
+
+        Ok((
+            DataChangeRecord {
+                previous_hash,
+                data_hash,
+                hash_algo,
+                actor,
+                timestamp,
             },
             remainder,
         ))
@@ -316,6 +1080,50 @@ fn get_workflows_dict() -> URef {
         .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
 }
 
+/// Read an active workflow.
+///
+/// Casper dictionaries cannot delete items, so archiving overwrites the
+/// active entry with a unit tombstone; it is reported as absent here.
+fn read_workflow(key: &str) -> Option<WorkflowData> {
+    let workflows_dict = get_workflows_dict();
+    match storage::dictionary_get::<WorkflowData>(workflows_dict, key) {
+        Ok(workflow) => workflow,
+        // Only the empty tombstone decodes as unit
+        Err(_) => match storage::dictionary_get::<()>(workflows_dict, key) {
+            Ok(Some(())) => None,
+            _ => runtime::revert(ApiError::User(WorkflowError::StorageError as u16)),
+        },
+    }
+}
+
+/// Read an active workflow, reverting if it does not exist or is archived.
+fn load_workflow(key: &str) -> WorkflowData {
+    read_workflow(key).unwrap_or_revert_with(ApiError::User(WorkflowError::WorkflowNotFound as u16))
+}
+
+/// Revert if a workflow has been voided.
+fn require_not_voided(workflow: &WorkflowData) {
+    if workflow.is_voided {
+        runtime::revert(ApiError::User(WorkflowError::WorkflowVoided as u16));
+    }
+}
+
+/// Get the workflows archive dictionary URef.
+fn get_workflows_archive_dict() -> URef {
+    runtime::get_key(WORKFLOWS_ARCHIVE_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the transitions archive dictionary URef.
+fn get_transitions_archive_dict() -> URef {
+    runtime::get_key(TRANSITIONS_ARCHIVE_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
 /// Get the transitions dictionary URef.
 fn get_transitions_dict() -> URef {
     runtime::get_key(TRANSITIONS_DICT)
@@ -324,6 +1132,21 @@ fn get_transitions_dict() -> URef {
         .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
 }
 
+/// Get the data changes dictionary URef.
+fn get_data_changes_dict() -> URef {
+    runtime::get_key(DATA_CHANGES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read the data hash changes of a workflow, oldest first.
+fn read_data_changes(key: &str) -> Vec<DataChangeRecord> {
+    storage::dictionary_get(get_data_changes_dict(), key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default()
+}
+
 /// Get the compliance proofs dictionary URef.
 fn get_compliance_proofs_dict() -> URef {
     runtime::get_key(COMPLIANCE_PROOFS_DICT)
@@ -332,452 +1155,8463 @@ fn get_compliance_proofs_dict() -> URef {
         .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
 }
 
-/// Get current workflow count.
-fn read_workflow_count() -> U256 {
-    let uref = runtime::get_key(WORKFLOW_COUNT_KEY)
+/// Get the creator index dictionary URef.
+fn get_creator_index_dict() -> URef {
+    runtime::get_key(CREATOR_INDEX_DICT)
         .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
         .into_uref()
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
-    storage::read(uref)
         .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
-        .unwrap_or(U256::zero())
 }
 
-/// Increment and return new workflow count.
-fn increment_workflow_count() -> U256 {
-    let uref = runtime::get_key(WORKFLOW_COUNT_KEY)
+/// Get the escalation index dictionary URef.
+fn get_escalation_index_dict() -> URef {
+    runtime::get_key(ESCALATION_INDEX_DICT)
         .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
         .into_uref()
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
-    
-    let current = read_workflow_count();
-    let new_count = current
-        .checked_add(U256::one())
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::Overflow as u16));
-    
-    storage::write(uref, new_count);
-    new_count
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
 }
 
-/// Check if a state is terminal (workflow complete).
-fn is_terminal_state(state: u8) -> bool {
-    matches!(state, states::APPROVED | states::REJECTED | states::CANCELLED)
+/// Read the IDs of the escalated workflows assigned to an approver.
+fn read_escalated_for(assignee: AccountHash) -> Vec<U256> {
+    storage::dictionary_get(get_escalation_index_dict(), &assignee.to_string())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default()
 }
 
-/// Validate state transition is allowed.
-/// This implements the basic state machine logic.
-/// More complex transition rules should be validated off-chain.
-fn is_valid_transition(from: u8, to: u8) -> bool {
-    match (from, to) {
-        // From DRAFT
-        (states::DRAFT, states::PENDING_REVIEW) => true,
-        (states::DRAFT, states::CANCELLED) => true,
-        
-        // From PENDING_REVIEW
-        (states::PENDING_REVIEW, states::APPROVED) => true,
-        (states::PENDING_REVIEW, states::REJECTED) => true,
-        (states::PENDING_REVIEW, states::ESCALATED) => true,
-        
-        // From ESCALATED
-        (states::ESCALATED, states::APPROVED) => true,
-        (states::ESCALATED, states::REJECTED) => true,
-        
-        // No other transitions allowed
-        _ => false,
+/// Read the IDs of the workflows created by an account, oldest first.
+fn read_creator_workflows(creator: AccountHash) -> Vec<U256> {
+    storage::dictionary_get(get_creator_index_dict(), &creator.to_string())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default()
+}
+
+/// Get the decision categories dictionary URef.
+fn get_categories_dict() -> URef {
+    runtime::get_key(CATEGORIES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the category-required flag URef.
+fn get_category_required_uref() -> URef {
+    runtime::get_key(CATEGORY_REQUIRED_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Check whether terminal transitions must supply a registered category.
+fn read_category_required() -> bool {
+    storage::read(get_category_required_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(false)
+}
+
+/// Get the intake-approval flag URef.
+fn get_intake_approval_uref() -> URef {
+    runtime::get_key(INTAKE_APPROVAL_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Check whether new workflows require intake approval.
+fn read_intake_approval() -> bool {
+    storage::read(get_intake_approval_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(false)
+}
+
+/// Get the restricted-creation flag URef.
+fn get_restricted_creation_uref() -> URef {
+    runtime::get_key(RESTRICTED_CREATION_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Check whether workflow creation is limited to REQUESTER accounts.
+fn read_restricted_creation() -> bool {
+    storage::read(get_restricted_creation_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(false)
+}
+
+/// Check that a caller may create workflows: anyone when creation is open,
+/// otherwise only holders of REQUESTER. `caller_mask` is only read for
+/// restricted creation.
+fn check_may_create<F: Fn() -> u64>(restricted: bool, caller_mask: F) -> Result<(), WorkflowError> {
+    if restricted && caller_mask() & roles::REQUESTER == 0 {
+        return Err(WorkflowError::InsufficientPermissions);
     }
+    Ok(())
 }
 
-/// Get current block timestamp.
-/// Note: In Casper, we use the blocktime from runtime.
-fn get_block_time() -> u64 {
-    runtime::get_blocktime().into()
+/// Get the paused flag URef.
+fn get_paused_uref() -> URef {
+    runtime::get_key(PAUSED_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
 }
 
-// =============================================================================
-// Entry Points
-// =============================================================================
+/// Check whether the contract is paused.
+fn read_paused() -> bool {
+    storage::read(get_paused_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(false)
+}
 
-/// Create a new workflow instance.
-///
-/// # Arguments
-///
-/// * `template_hash` - 32-byte hash of the workflow template definition
-/// * `data_hash` - 32-byte hash of the associated business data
-///
-/// # Returns
-///
+/// Revert if the contract is paused.
+fn require_not_paused() {
+    if read_paused() {
+        runtime::revert(ApiError::User(WorkflowError::ContractPaused as u16));
+    }
+}
+
+/// Get the rate limit configuration URef.
+fn get_rate_limit_uref() -> URef {
+    runtime::get_key(RATE_LIMIT_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the call budget dictionary URef.
+fn get_call_budget_dict() -> URef {
+    runtime::get_key(CALL_BUDGET_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the processing locks dictionary URef.
+fn get_locked_dict() -> URef {
+    runtime::get_key(LOCKED_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Mark a workflow as being processed, reverting if it already is.
+///
+/// Deploys execute sequentially, so this only trips on a nested call into
+/// the contract (e.g. future session code or a contract-to-contract path)
+/// that reaches the same workflow before the outer call has finished.
+fn lock_workflow(key: &str) {
+    let locks_dict = get_locked_dict();
+    let locked: Option<bool> = storage::dictionary_get(locks_dict, key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    if locked == Some(true) {
+        runtime::revert(ApiError::User(WorkflowError::WorkflowLocked as u16));
+    }
+    storage::dictionary_put(locks_dict, key, true);
+}
+
+/// Release a workflow's processing lock.
+fn unlock_workflow(key: &str) {
+    storage::dictionary_put(get_locked_dict(), key, false);
+}
+
+/// Count a mutating call against the caller's budget for the current
+/// window, reverting once the budget is exhausted.
+fn consume_call_budget() {
+    let (budget, window_seconds): (u32, u64) = storage::read(get_rate_limit_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or((0, 0));
+    if budget == 0 {
+        return;
+    }
+    
+    let window = budget_window(get_block_time(), window_seconds);
+    let key = format!("{}_{}", runtime::get_caller(), window);
+    let budget_dict = get_call_budget_dict();
+    
+    let used: u32 = storage::dictionary_get(budget_dict, &key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(0);
+    let used = spend_call(used, budget)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::RateLimited as u16));
+    storage::dictionary_put(budget_dict, &key, used);
+}
+
+/// Index of the rate-limit window containing blocktime `now` (ms).
+fn budget_window(now: u64, window_seconds: u64) -> u64 {
+    now / window_seconds.saturating_mul(1000).max(1)
+}
+
+/// Calls used in a window after one more, or None once `budget` is spent.
+fn spend_call(used: u32, budget: u32) -> Option<u32> {
+    if used >= budget {
+        return None;
+    }
+    Some(used + 1)
+}
+
+/// Check whether a decision category has been registered.
+fn is_registered_category(category_id: u32) -> bool {
+    let existing: Option<[u8; 32]> =
+        storage::dictionary_get(get_categories_dict(), &category_id.to_string())
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    existing.is_some()
+}
+
+/// Get the transition rules dictionary URef.
+fn get_transition_rules_dict() -> URef {
+    runtime::get_key(TRANSITION_RULES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the self-approval dictionary URef.
+fn get_self_approval_dict() -> URef {
+    runtime::get_key(SELF_APPROVAL_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Check whether a template lets creators approve their own workflows.
+/// Segregation of duties is enforced unless the template opted out.
+fn is_self_approval_allowed(template_hash: &[u8; 32]) -> bool {
+    storage::dictionary_get(get_self_approval_dict(), &hash_key(template_hash))
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(false)
+}
+
+/// Get the comment-required dictionary URef.
+fn get_comment_required_dict() -> URef {
+    runtime::get_key(COMMENT_REQUIRED_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Check that a transition into `to_state` carries a comment when the
+/// template requires one for that state.
+fn check_comment_required(
+    template_hash: &[u8; 32],
+    to_state: u8,
+    comment_hash: &[u8; 32],
+) -> Result<(), WorkflowError> {
+    if *comment_hash != [0u8; 32] {
+        return Ok(());
+    }
+    let required: Vec<u8> =
+        storage::dictionary_get(get_comment_required_dict(), &hash_key(template_hash))
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+            .unwrap_or_default();
+    match_comment_required(&required, to_state, comment_hash)
+}
+
+/// Check a comment hash against the states a template requires comments for.
+fn match_comment_required(
+    required: &[u8],
+    to_state: u8,
+    comment_hash: &[u8; 32],
+) -> Result<(), WorkflowError> {
+    if *comment_hash == [0u8; 32] && required.contains(&to_state) {
+        return Err(WorkflowError::CommentRequired);
+    }
+    Ok(())
+}
+
+/// Get the rule conditions dictionary URef.
+fn get_rule_conditions_dict() -> URef {
+    runtime::get_key(RULE_CONDITIONS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Check that a transition supplies the condition hash its template rule
+/// expects, if the rule specifies one.
+fn check_rule_condition(
+    template_hash: &[u8; 32],
+    from_state: u8,
+    to_state: u8,
+    condition_hash: Option<[u8; 32]>,
+) -> Result<(), WorkflowError> {
+    let conditions: Vec<RuleCondition> =
+        storage::dictionary_get(get_rule_conditions_dict(), &hash_key(template_hash))
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+            .unwrap_or_default();
+    match_rule_condition(&conditions, from_state, to_state, condition_hash)
+}
+
+/// Check a supplied condition hash against a template's rule conditions.
+fn match_rule_condition(
+    conditions: &[RuleCondition],
+    from_state: u8,
+    to_state: u8,
+    condition_hash: Option<[u8; 32]>,
+) -> Result<(), WorkflowError> {
+    let expected = conditions
+        .iter()
+        .find(|(from, to, _)| *from == from_state && *to == to_state)
+        .map(|(_, _, expected)| *expected);
+    match expected {
+        Some(expected) if condition_hash != Some(expected) => Err(WorkflowError::ConditionNotMet),
+        _ => Ok(()),
+    }
+}
+
+/// Get the template approvers dictionary URef.
+fn get_template_approvers_dict() -> URef {
+    runtime::get_key(TEMPLATE_APPROVERS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Check whether an account is in a template's approver pool.
+/// Templates without a configured pool accept any approver.
+fn is_template_approver(template_hash: &[u8; 32], account: AccountHash) -> bool {
+    let approvers: Vec<AccountHash> =
+        storage::dictionary_get(get_template_approvers_dict(), &hash_key(template_hash))
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+            .unwrap_or_default();
+    in_approver_pool(&approvers, account)
+}
+
+/// Check whether an account is in an approver pool; an empty pool accepts
+/// any approver.
+fn in_approver_pool(approvers: &[AccountHash], account: AccountHash) -> bool {
+    approvers.is_empty() || approvers.contains(&account)
+}
+
+/// Blake2b-256 digest of `data` via the host function.
+#[cfg(not(test))]
+fn blake2b<T: AsRef<[u8]>>(data: T) -> [u8; 32] {
+    runtime::blake2b(data)
+}
+
+/// Blake2b-256 digest of `data`; unit tests run without the host.
+#[cfg(test)]
+fn blake2b<T: AsRef<[u8]>>(data: T) -> [u8; 32] {
+    casper_types::Digest::hash(data).value()
+}
+
+/// Encode a 32-byte hash as a lowercase hex dictionary key.
+fn hash_key(hash: &[u8; 32]) -> String {
+    let mut key = String::with_capacity(64);
+    for byte in hash.iter() {
+        key.push_str(&format!("{:02x}", byte));
+    }
+    key
+}
+
+/// Get the seen transition requests dictionary URef.
+fn get_seen_requests_dict() -> URef {
+    runtime::get_key(SEEN_REQUESTS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Dictionary key for a workflow-scoped request ID.
+/// Hashed because the decimal ID plus the hex request ID can exceed the
+/// dictionary item key length limit.
+fn request_key(workflow_id: U256, request_id: &[u8; 32]) -> String {
+    let mut preimage = workflow_id
+        .to_bytes()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::SerializationError as u16));
+    preimage.extend_from_slice(request_id);
+    hash_key(&blake2b(preimage))
+}
+
+/// Get the time-in-state aggregates dictionary URef.
+fn get_time_in_state_dict() -> URef {
+    runtime::get_key(TIME_IN_STATE_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read the (total dwell time, sample count) aggregate for a state.
+fn read_time_in_state(state: u8) -> (u64, u64) {
+    storage::dictionary_get(get_time_in_state_dict(), &state.to_string())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or((0, 0))
+}
+
+/// Add one dwell-time sample for a state being left.
+fn record_time_in_state(state: u8, duration: u64) {
+    let aggregate = add_time_sample(read_time_in_state(state), duration)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::Overflow as u16));
+    storage::dictionary_put(get_time_in_state_dict(), &state.to_string(), aggregate);
+}
+
+/// Add a dwell-time sample to a (total, samples) aggregate, or None on
+/// overflow.
+fn add_time_sample((total, samples): (u64, u64), duration: u64) -> Option<(u64, u64)> {
+    Some((total.checked_add(duration)?, samples.checked_add(1)?))
+}
+
+/// Average dwell time of a (total, samples) aggregate (0 without samples).
+fn average_time((total, samples): (u64, u64)) -> u64 {
+    total.checked_div(samples).unwrap_or(0)
+}
+
+/// Get the state SLA dictionary URef.
+fn get_state_slas_dict() -> URef {
+    runtime::get_key(STATE_SLAS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read the SLA in seconds configured for a template's state (0 = none).
+fn read_state_sla(template_hash: &[u8; 32], state: u8) -> u64 {
+    let key = format!("{}_{}", hash_key(template_hash), state);
+    storage::dictionary_get(get_state_slas_dict(), &key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(0)
+}
+
+/// Get the terminal states dictionary URef.
+fn get_terminal_states_dict() -> URef {
+    runtime::get_key(TERMINAL_STATES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the terminal grace dictionary URef.
+fn get_terminal_grace_dict() -> URef {
+    runtime::get_key(TERMINAL_GRACE_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read a template's grace period in seconds before terminal workflows
+/// are finalized (0 when they complete immediately).
+fn read_terminal_grace(template_hash: &[u8; 32]) -> u64 {
+    storage::dictionary_get(get_terminal_grace_dict(), &hash_key(template_hash))
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(0)
+}
+
+/// Get the minimum transition intervals dictionary URef.
+fn get_min_transition_intervals_dict() -> URef {
+    runtime::get_key(MIN_TRANSITION_INTERVALS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read the minimum seconds a template's workflows must wait after their
+/// last update before transitioning again (0 when unthrottled).
+fn read_min_transition_interval(template_hash: &[u8; 32]) -> u64 {
+    storage::dictionary_get(get_min_transition_intervals_dict(), &hash_key(template_hash))
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(0)
+}
+
+/// Check whether a workflow's terminal grace period has ended at `now`,
+/// after which it can no longer be reopened.
+fn is_grace_over(workflow: &WorkflowData, now: u64) -> bool {
+    workflow.finalizes_at != 0 && now >= workflow.finalizes_at
+}
+
+/// Check that a workflow awaits finalization and its grace period has
+/// ended at `now`.
+fn check_finalize(workflow: &WorkflowData, now: u64) -> Result<(), WorkflowError> {
+    if workflow.is_completed || workflow.finalizes_at == 0 {
+        return Err(WorkflowError::InvalidTransition);
+    }
+    if !is_grace_over(workflow, now) {
+        return Err(WorkflowError::GracePeriodActive);
+    }
+    Ok(())
+}
+
+/// Check if an open workflow's deadline has passed at `now`.
+fn is_overdue(workflow: &WorkflowData, now: u64) -> bool {
+    !workflow.is_completed
+        && !workflow.is_voided
+        && workflow.deadline != 0
+        && now > workflow.deadline
+}
+
+/// Compute the deadline for an SLA starting now (0 when there is no SLA).
+/// Blocktime is in milliseconds, SLAs are in seconds.
+fn sla_deadline(now: u64, sla_seconds: u64) -> u64 {
+    checked_sla_deadline(now, sla_seconds)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::Overflow as u16))
+}
+
+/// `sla_deadline`, or None on overflow.
+fn checked_sla_deadline(now: u64, sla_seconds: u64) -> Option<u64> {
+    if sla_seconds == 0 {
+        return Some(0);
+    }
+    sla_seconds
+        .checked_mul(1000)
+        .and_then(|sla_millis| now.checked_add(sla_millis))
+}
+
+/// Get the pending approvals dictionary URef.
+fn get_pending_approvals_dict() -> URef {
+    runtime::get_key(PENDING_APPROVALS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Record an approval vote towards a workflow's quorum.
+/// Returns true once the number of distinct approvers meets `required`.
+fn record_approval(key: &str, approver: AccountHash, required: u8) -> bool {
+    let approvals_dict = get_pending_approvals_dict();
+    let mut approvals: Vec<AccountHash> = storage::dictionary_get(approvals_dict, key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default();
+    
+    if approvals.contains(&approver) {
+        runtime::revert(ApiError::User(WorkflowError::DuplicateApproval as u16));
+    }
+    approvals.push(approver);
+    
+    let reached = approvals.len() >= required as usize;
+    storage::dictionary_put(approvals_dict, key, approvals);
+    reached
+}
+
+/// Read the approvers who have voted towards a workflow's open quorum.
+fn read_approvals(key: &str) -> Vec<AccountHash> {
+    storage::dictionary_get(get_pending_approvals_dict(), key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default()
+}
+
+/// Check whether an account has already voted towards a workflow's quorum.
+fn has_approved(key: &str, approver: AccountHash) -> bool {
+    read_approvals(key).contains(&approver)
+}
+
+/// Get the deadline windows URef.
+fn get_deadline_windows_uref() -> URef {
+    runtime::get_key(DEADLINE_WINDOWS_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the deadline buckets dictionary URef.
+fn get_deadline_buckets_dict() -> URef {
+    runtime::get_key(DEADLINE_BUCKETS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read the windows holding open deadlines, in ascending order.
+fn read_deadline_windows() -> Vec<u64> {
+    storage::read(get_deadline_windows_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default()
+}
+
+/// Read the open deadlines of a window, sorted by (deadline, workflow_id).
+fn read_deadline_bucket(window: u64) -> Vec<(u64, U256)> {
+    storage::dictionary_get(get_deadline_buckets_dict(), &window.to_string())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default()
+}
+
+/// Get the index window a deadline falls in.
+fn deadline_window(deadline: u64) -> u64 {
+    deadline / DEADLINE_WINDOW_MS
+}
+
+/// Move a workflow's entry in the deadline index from `old` to `new`.
+/// A zero deadline means the workflow is not indexed.
+///
+/// Deadlines are bucketed by window, so a move only rewrites the affected
+/// buckets; the window list changes only when a bucket fills or empties.
+fn update_deadline_index(workflow_id: U256, old: u64, new: u64) {
+    if old == new {
+        return;
+    }
+    let buckets_dict = get_deadline_buckets_dict();
+    let mut windows = read_deadline_windows();
+    let mut windows_changed = false;
+    
+    if old != 0 {
+        let window = deadline_window(old);
+        let mut bucket = read_deadline_bucket(window);
+        if let Ok(position) = bucket.binary_search(&(old, workflow_id)) {
+            bucket.remove(position);
+            if bucket.is_empty() {
+                if let Ok(position) = windows.binary_search(&window) {
+                    windows.remove(position);
+                    windows_changed = true;
+                }
+            }
+            storage::dictionary_put(buckets_dict, &window.to_string(), bucket);
+        }
+    }
+    if new != 0 {
+        let window = deadline_window(new);
+        let mut bucket = read_deadline_bucket(window);
+        if let Err(position) = bucket.binary_search(&(new, workflow_id)) {
+            bucket.insert(position, (new, workflow_id));
+            if let Err(position) = windows.binary_search(&window) {
+                windows.insert(position, window);
+                windows_changed = true;
+            }
+            storage::dictionary_put(buckets_dict, &window.to_string(), bucket);
+        }
+    }
+    if windows_changed {
+        storage::write(get_deadline_windows_uref(), windows);
+    }
+}
+
+/// Collect up to `limit` workflow IDs with a deadline before `now`, most
+/// overdue first, reading each window's bucket through `read_bucket`.
+fn collect_past_deadline<F: Fn(u64) -> Vec<(u64, U256)>>(
+    windows: &[u64],
+    read_bucket: F,
+    now: u64,
+    limit: usize,
+) -> Vec<U256> {
+    let mut breached = Vec::new();
+    for window in windows {
+        // Later windows start at or after `now`
+        if window.saturating_mul(DEADLINE_WINDOW_MS) >= now || breached.len() >= limit {
+            break;
+        }
+        breached.extend(
+            read_bucket(*window)
+                .into_iter()
+                .take_while(|(deadline, _)| *deadline < now)
+                .take(limit - breached.len())
+                .map(|(_, workflow_id)| workflow_id),
+        );
+    }
+    breached
+}
+
+/// Get the role registry dictionary URef.
+fn get_roles_dict() -> URef {
+    runtime::get_key(ROLES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read the registered role mask of an account (0 if unregistered).
+fn read_role_mask(account: AccountHash) -> u64 {
+    storage::dictionary_get(get_roles_dict(), &account.to_string())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(0)
+}
+
+/// Get the delegations dictionary URef.
+fn get_delegations_dict() -> URef {
+    runtime::get_key(DELEGATIONS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the delegates reverse index dictionary URef.
+fn get_delegates_dict() -> URef {
+    runtime::get_key(DELEGATES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read the accounts that have delegated authority to `delegate`.
+fn read_delegators(delegate: AccountHash) -> Vec<AccountHash> {
+    storage::dictionary_get(get_delegates_dict(), &delegate.to_string())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default()
+}
+
+/// Resolve the roles an account may act with for transitions: its own
+/// registered roles plus any unexpired delegations it has received.
+/// Delegated roles are limited to those the delegator still holds.
+fn effective_role_mask(account: AccountHash) -> u64 {
+    let now = get_block_time();
+    let delegations_dict = get_delegations_dict();
+    
+    let mut mask = read_role_mask(account);
+    for delegator in read_delegators(account) {
+        let delegation: Option<(AccountHash, u64, u64)> =
+            storage::dictionary_get(delegations_dict, &delegator.to_string())
+                .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+        if let Some(delegation) = delegation {
+            mask |= delegated_mask(delegation, account, read_role_mask(delegator), now);
+        }
+    }
+    mask
+}
+
+/// Roles a delegation grants `account` at `now`: none unless it names the
+/// account and is unexpired, and never more than the delegator holds.
+fn delegated_mask(
+    (delegate, expires_at, role_mask): (AccountHash, u64, u64),
+    account: AccountHash,
+    delegator_mask: u64,
+    now: u64,
+) -> u64 {
+    if delegate == account && now < expires_at {
+        role_mask & delegator_mask
+    } else {
+        0
+    }
+}
+
+/// Check that `caller`, holding `held`, may delegate `role_mask` to
+/// `delegate` until `expires_at`.
+fn check_delegation(
+    caller: AccountHash,
+    held: u64,
+    delegate: AccountHash,
+    role_mask: u64,
+    expires_at: u64,
+    now: u64,
+) -> Result<(), WorkflowError> {
+    if role_mask == 0 || delegate == caller || expires_at <= now {
+        return Err(WorkflowError::InvalidArgument);
+    }
+    
+    // Only roles the caller holds directly can be delegated
+    if role_mask & !held != 0 {
+        return Err(WorkflowError::InsufficientPermissions);
+    }
+    Ok(())
+}
+
+/// Store an account's role mask and record the change in the audit stream.
+fn write_role_mask(account: AccountHash, role_mask: u64, actor: AccountHash) {
+    storage::dictionary_put(get_roles_dict(), &account.to_string(), role_mask);
+    
+    record_logged_change(
+        audit_kinds::ROLE_CHANGE,
+        get_role_changes_dict(),
+        (account, role_mask, (actor, get_block_time())),
+    );
+}
+
+/// Get the admin account URef.
+fn get_admin_uref() -> URef {
+    runtime::get_key(ADMIN_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the pending admin URef.
+fn get_pending_admin_uref() -> URef {
+    runtime::get_key(PENDING_ADMIN_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read the admin account.
+fn read_admin() -> AccountHash {
+    storage::read(get_admin_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Revert unless the caller is the admin account.
+fn require_admin() {
+    if runtime::get_caller() != read_admin() {
+        runtime::revert(ApiError::User(WorkflowError::InsufficientPermissions as u16));
+    }
+}
+
+/// Hash a transition record for chaining.
+fn transition_hash(transition: &TransitionRecord) -> [u8; 32] {
+    let bytes = transition
+        .to_bytes()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::SerializationError as u16));
+    blake2b(bytes)
+}
+
+/// Check that every record's prev_hash equals `hash` of the record before
+/// it, the first record linking to `anchor`.
+fn chain_intact<F: Fn(&TransitionRecord) -> [u8; 32]>(
+    transitions: &[TransitionRecord],
+    anchor: [u8; 32],
+    hash: F,
+) -> bool {
+    let mut expected = anchor;
+    for transition in transitions.iter() {
+        if transition.prev_hash != expected {
+            return false;
+        }
+        expected = hash(transition);
+    }
+    true
+}
+
+/// Get the history length limit URef.
+fn get_max_history_len_uref() -> URef {
+    runtime::get_key(MAX_HISTORY_LEN_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the escalation limit URef.
+fn get_max_escalations_uref() -> URef {
+    runtime::get_key(MAX_ESCALATIONS_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read the maximum number of escalations per workflow.
+fn read_max_escalations() -> u8 {
+    storage::read(get_max_escalations_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(DEFAULT_MAX_ESCALATIONS)
+}
+
+/// Append a transition to a workflow's history, linking it to the
+/// previous record's hash. Returns the record's index in the history.
+/// Reverts once the history has reached its length limit.
+fn append_transition(key: &str, mut transition: TransitionRecord) -> u64 {
+    let transitions_dict = get_transitions_dict();
+    let mut transitions: Vec<TransitionRecord> = storage::dictionary_get(transitions_dict, key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default();
+    
+    let max_len: u32 = storage::read(get_max_history_len_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(DEFAULT_MAX_HISTORY_LEN);
+    if !has_history_room(transitions.len(), max_len) {
+        runtime::revert(ApiError::User(WorkflowError::HistoryLimitExceeded as u16));
+    }
+    
+    transition.prev_hash = transitions.last().map(transition_hash).unwrap_or([0u8; 32]);
+    transition.block_height = runtime::get_block_height();
+    transitions.push(transition);
+    let index = transitions.len() as u64 - 1;
+    storage::dictionary_put(transitions_dict, key, transitions);
+    index
+}
+
+/// Check whether a history of `len` records can take another under a
+/// length limit of `max_len`.
+fn has_history_room(len: usize, max_len: u32) -> bool {
+    len < max_len as usize
+}
+
+/// Get the comments dictionary URef.
+fn get_comments_dict() -> URef {
+    runtime::get_key(COMMENTS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the comment length limit URef.
+fn get_comment_max_len_uref() -> URef {
+    runtime::get_key(COMMENT_MAX_LEN_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Key a transition's comment by workflow ID and history index. Keys use
+/// the position in the full history, so they survive purges of older
+/// records.
+fn comment_key(workflow_id: U256, index: u64) -> String {
+    let (purged, _) = read_history_purge(workflow_id);
+    format!("{}:{}", workflow_id, purged.saturating_add(index))
+}
+
+/// Get the history purges dictionary URef.
+fn get_history_purges_dict() -> URef {
+    runtime::get_key(HISTORY_PURGES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the purge log dictionary URef.
+fn get_purge_log_dict() -> URef {
+    runtime::get_key(PURGE_LOG_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read how many records were purged from the front of a workflow's
+/// history, and the hash of the last one, which the oldest retained
+/// record's prev_hash links to ((0, zero hash) when never purged).
+fn read_history_purge(workflow_id: U256) -> (u64, [u8; 32]) {
+    storage::dictionary_get(get_history_purges_dict(), &workflow_id.to_string())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or((0, [0u8; 32]))
+}
+
+/// Hash comment text with one of the `hash_algos`. SHA-256 is not
+/// available on-chain, so comments hashed with it cannot be verified.
+fn comment_digest(comment: &str, hash_algo: u8) -> Option<[u8; 32]> {
+    match hash_algo {
+        hash_algos::KECCAK256 => {
+            let mut digest = [0u8; 32];
+            let mut keccak = Keccak::v256();
+            keccak.update(comment.as_bytes());
+            keccak.finalize(&mut digest);
+            Some(digest)
+        }
+        hash_algos::BLAKE2B => Some(blake2b(comment.as_bytes())),
+        _ => None,
+    }
+}
+
+/// Revert unless a comment fits the length limit and hashes, with the
+/// record's hash_algo, to the transition's comment_hash.
+fn validate_comment(comment: &str, comment_hash: &[u8; 32], hash_algo: u8) {
+    let max_len: u32 = storage::read(get_comment_max_len_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(DEFAULT_COMMENT_MAX_LEN);
+    if comment.len() > max_len as usize || comment_digest(comment, hash_algo) != Some(*comment_hash)
+    {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+}
+
+/// Compute a workflow's audit digest: the hash of its current record
+/// together with the head of its transition hash chain.
+fn workflow_digest(key: &str) -> Option<[u8; 32]> {
+    let workflow = read_workflow(key)?;
+    let transitions: Vec<TransitionRecord> = storage::dictionary_get(get_transitions_dict(), key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default();
+    
+    let mut bytes = workflow
+        .to_bytes()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::SerializationError as u16));
+    bytes.extend_from_slice(&transitions.last().map(transition_hash).unwrap_or([0u8; 32]));
+    Some(blake2b(bytes))
+}
+
+/// Get the global Merkle leaves dictionary URef.
+fn get_global_leaves_dict() -> URef {
+    runtime::get_key(GLOBAL_LEAVES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the global Merkle root URef.
+fn get_global_root_uref() -> URef {
+    runtime::get_key(GLOBAL_ROOT_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the global Merkle internal nodes dictionary URef.
+fn get_global_nodes_dict() -> URef {
+    runtime::get_key(GLOBAL_NODES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the global Merkle tree size URef.
+fn get_global_tree_size_uref() -> URef {
+    runtime::get_key(GLOBAL_TREE_SIZE_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read a stored node of the global Merkle tree; level 0 holds the leaves,
+/// at position workflow_id - 1. Missing nodes are zero.
+fn read_global_node(level: u32, index: u64) -> [u8; 32] {
+    let node: Option<[u8; 32]> = if level == 0 {
+        let key = (U256::from(index) + U256::one()).to_string();
+        storage::dictionary_get(get_global_leaves_dict(), &key)
+    } else {
+        storage::dictionary_get(get_global_nodes_dict(), &format!("{}:{}", level, index))
+    }
+    .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    node.unwrap_or([0u8; 32])
+}
+
+/// Hash two sibling Merkle nodes into their parent.
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut bytes = Vec::with_capacity(64);
+    bytes.extend_from_slice(left);
+    bytes.extend_from_slice(right);
+    blake2b(bytes)
+}
+
+/// A recomputed Merkle tree node: (level, index, node)
+type MerkleNode = (u32, u64, [u8; 32]);
+
+/// Recompute the path from changed leaves to the root of a Merkle tree
+/// over `size` leaves. Each level hashes adjacent pairs; an odd node at the
+/// end of a level is carried up unchanged.
+///
+/// Leaves `lo..=hi` (below `size`) changed or were appended; `read(level,
+/// index)` returns every other stored node, level 0 being the leaves. Only
+/// the O(log n) nodes above the changed range are recomputed. Returns the
+/// new nodes above the leaves as (level, index, node), and the root.
+fn merkle_update<F: Fn(u32, u64) -> [u8; 32]>(
+    size: u64,
+    lo: u64,
+    hi: u64,
+    read: F,
+) -> (Vec<MerkleNode>, [u8; 32]) {
+    let mut updated = Vec::new();
+    let (mut lo, mut hi) = (lo, hi);
+    let mut level = 0u32;
+    let mut width = size;
+    let mut changed: Vec<[u8; 32]> = (lo..=hi).map(|index| read(0, index)).collect();
+    
+    while width > 1 {
+        let node = |index: u64| {
+            if (lo..=hi).contains(&index) {
+                changed[(index - lo) as usize]
+            } else {
+                read(level, index)
+            }
+        };
+        let parents: Vec<[u8; 32]> = (lo / 2..=hi / 2)
+            .map(|parent| {
+                let left = node(2 * parent);
+                if 2 * parent + 1 < width {
+                    hash_pair(&left, &node(2 * parent + 1))
+                } else {
+                    left
+                }
+            })
+            .collect();
+        
+        level += 1;
+        width = width.div_ceil(2);
+        lo /= 2;
+        hi /= 2;
+        for (offset, parent) in parents.iter().enumerate() {
+            updated.push((level, lo + offset as u64, *parent));
+        }
+        changed = parents;
+    }
+    
+    (updated, changed[0])
+}
+
+/// Get the audit stream sequence counter URef.
+fn get_audit_seq_uref() -> URef {
+    runtime::get_key(AUDIT_SEQ_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the audit stream dictionary URef.
+fn get_audit_stream_dict() -> URef {
+    runtime::get_key(AUDIT_STREAM_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the role changes dictionary URef.
+fn get_role_changes_dict() -> URef {
+    runtime::get_key(ROLE_CHANGES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the metadata dictionary URef.
+fn get_metadata_dict() -> URef {
+    runtime::get_key(METADATA_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the metadata changes dictionary URef.
+fn get_metadata_changes_dict() -> URef {
+    runtime::get_key(METADATA_CHANGES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the creator changes dictionary URef.
+fn get_creator_changes_dict() -> URef {
+    runtime::get_key(CREATOR_CHANGES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the original creators dictionary URef.
+fn get_original_creators_dict() -> URef {
+    runtime::get_key(ORIGINAL_CREATORS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read the account that created a workflow, which differs from the
+/// current creator once the workflow has been reassigned.
+fn read_original_creator(workflow: &WorkflowData) -> AccountHash {
+    storage::dictionary_get(get_original_creators_dict(), &workflow.id.to_string())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(workflow.creator)
+}
+
+/// Key a workflow attachment by workflow ID and attribute name, reverting
+/// if the name is empty or the key exceeds Casper's 64-byte item key limit.
+fn metadata_key(workflow_id: U256, attr_name: &str) -> String {
+    let key = format!("{}:{}", workflow_id, attr_name);
+    if attr_name.is_empty() || key.len() > 64 {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    key
+}
+
+/// Allocate the next audit stream sequence number.
+fn next_audit_seq() -> u64 {
+    let seq_uref = get_audit_seq_uref();
+    let last: u64 = storage::read(seq_uref)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(0);
+    let seq = last
+        .checked_add(1)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::Overflow as u16));
+    storage::write(seq_uref, seq);
+    seq
+}
+
+/// Append an entry to the audit stream, returning its sequence number.
+fn record_audit_entry(kind: u8, reference: U256) -> u64 {
+    let seq = next_audit_seq();
+    storage::dictionary_put(get_audit_stream_dict(), &seq.to_string(), (kind, reference));
+    seq
+}
+
+/// Store `entry` in the change log `log_dict` and append it to the audit
+/// stream, returning its sequence number. Logged changes have no other
+/// identifier, so the entry is keyed by, and referenced from the stream by,
+/// its own sequence number.
+fn record_logged_change<T: CLTyped + ToBytes>(kind: u8, log_dict: URef, entry: T) -> u64 {
+    let seq = next_audit_seq();
+    storage::dictionary_put(log_dict, &seq.to_string(), entry);
+    storage::dictionary_put(get_audit_stream_dict(), &seq.to_string(), (kind, U256::from(seq)));
+    seq
+}
+
+/// Collect up to `limit` audit entries with sequence numbers in
+/// (after_seq, last], in order, reading each through `read_entry`.
+fn collect_audit_entries<F: Fn(u64) -> Option<(u8, U256)>>(
+    after_seq: u64,
+    last: u64,
+    limit: u64,
+    read_entry: F,
+) -> Vec<(u8, U256)> {
+    let mut entries: Vec<(u8, U256)> = Vec::new();
+    let mut seq = after_seq.saturating_add(1);
+    while seq <= last && (entries.len() as u64) < limit {
+        if let Some(entry) = read_entry(seq) {
+            entries.push(entry);
+        }
+        seq += 1;
+    }
+    entries
+}
+
+/// Revert if the block time precedes the workflow's last update.
+fn require_monotonic(workflow: &WorkflowData, timestamp: u64) {
+    check_monotonic(workflow, timestamp).unwrap_or_revert();
+}
+
+/// Check that a block time does not precede the workflow's last update.
+fn check_monotonic(workflow: &WorkflowData, timestamp: u64) -> Result<(), WorkflowError> {
+    if timestamp < workflow.updated_at {
+        return Err(WorkflowError::TimestampRegression);
+    }
+    Ok(())
+}
+
+/// Apply a validated transition: update the workflow, append the record
+/// to its history and emit the transition event. Returns the record's
+/// index in the history.
+fn commit_transition(key: &str, workflow: &mut WorkflowData, transition: TransitionRecord) -> u64 {
+    let from_state = transition.from_state;
+    let to_state = transition.to_state;
+    let timestamp = transition.timestamp;
+    let actor = transition.actor;
+    
+    require_monotonic(workflow, timestamp);
+    
+    // Accumulate dwell time in the state being left
+    record_time_in_state(from_state, timestamp.saturating_sub(workflow.state_entered_at));
+    
+    // Update workflow state
+    let old_deadline = workflow.deadline;
+    workflow.current_state = to_state;
+    workflow.updated_at = timestamp;
+    workflow.state_entered_at = timestamp;
+    
+    // Leaving ESCALATED takes the workflow off the assignee's worklist
+    if from_state == states::ESCALATED {
+        if let Some(assignee) = workflow.assigned_approver.take() {
+            let mut assigned = read_escalated_for(assignee);
+            assigned.retain(|id| *id != workflow.id);
+            storage::dictionary_put(get_escalation_index_dict(), &assignee.to_string(), assigned);
+        }
+    }
+    if to_state == states::ESCALATED {
+        workflow.escalation_count = workflow.escalation_count.saturating_add(1);
+    }
+    // A terminal grace period defers completion until `finalize`
+    let is_terminal = is_template_terminal_state(&workflow.template_hash, to_state);
+    workflow.finalizes_at = if is_terminal {
+        sla_deadline(timestamp, read_terminal_grace(&workflow.template_hash))
+    } else {
+        0
+    };
+    workflow.is_completed = is_terminal && workflow.finalizes_at == 0;
+    workflow.deadline = if is_terminal {
+        0
+    } else {
+        sla_deadline(timestamp, read_state_sla(&workflow.template_hash, to_state))
+    };
+    update_deadline_index(workflow.id, old_deadline, workflow.deadline);
+    move_state_count(Some(from_state), to_state);
+    move_state_index(workflow.id, Some(from_state), to_state);
+    
+    // Store updated workflow
+    storage::dictionary_put(get_workflows_dict(), key, workflow.clone());
+    
+    // Append transition to history
+    let index = append_transition(key, transition);
+    record_audit_entry(audit_kinds::TRANSITION, workflow.id);
+    increment_total_transitions();
+    increment_transition_type_count(to_state);
+    
+    // Votes only count towards the review they were cast in, so leaving
+    // PENDING_REVIEW by any route discards them
+    if from_state == states::PENDING_REVIEW {
+        let no_approvals: Vec<AccountHash> = Vec::new();
+        storage::dictionary_put(get_pending_approvals_dict(), key, no_approvals);
+    }
+    
+    emit_transition_event(workflow.id, from_state, to_state, actor, timestamp);
+    if workflow.is_completed {
+        emit_event(
+            "workflow_completed",
+            &[
+                ("workflow_id", workflow.id.to_string()),
+                ("final_state", to_state.to_string()),
+                ("timestamp", timestamp.to_string()),
+            ],
+        );
+    }
+    index
+}
+
+/// Create and store a new workflow owned by the caller, returning its ID.
+/// The ID is `workflow_id` if given, otherwise the next auto-increment ID.
+/// `imported_state` places an imported workflow directly in that state,
+/// completing it if the state is terminal.
+fn store_new_workflow(
+    workflow_id: Option<U256>,
+    template_hash: [u8; 32],
+    data_hash: [u8; 32],
+    hash_algo: u8,
+    sla_seconds: Option<u64>,
+    required_approvals: u8,
+    imported_state: Option<u8>,
+) -> U256 {
+    // Get caller information
+    let caller = runtime::get_caller();
+    let timestamp = get_block_time();
+    
+    // Closed tenants only accept workflows from registered requesters;
+    // imports are already restricted to the admin
+    if imported_state.is_none() {
+        check_may_create(read_restricted_creation(), || effective_role_mask(caller))
+            .unwrap_or_revert();
+    }
+    
+    // Maker-checker intake holds new workflows until a reviewer admits them
+    let initial_state = match imported_state {
+        Some(state) => state,
+        None if read_intake_approval() => states::CREATED_PENDING,
+        None => states::DRAFT,
+    };
+    let is_completed = is_template_terminal_state(&template_hash, initial_state);
+    
+    // Derive the initial deadline; completed workflows have none
+    let sla_seconds = sla_seconds.unwrap_or_else(|| read_state_sla(&template_hash, initial_state));
+    let deadline = if is_completed {
+        0
+    } else {
+        sla_deadline(timestamp, sla_seconds)
+    };
+    
+    // Generate new workflow ID and its sequence number within the template
+    let workflow_id = match workflow_id {
+        Some(workflow_id) => {
+            claim_workflow_id(workflow_id);
+            workflow_id
+        }
+        None => increment_workflow_count(),
+    };
+    let template_seq = increment_template_count(&template_hash);
+    
+    // Create workflow data
+    let workflow = WorkflowData {
+        id: workflow_id,
+        template_hash,
+        data_hash,
+        current_state: initial_state,
+        creator: caller,
+        created_at: timestamp,
+        updated_at: timestamp,
+        is_completed,
+        deadline,
+        required_approvals,
+        state_entered_at: timestamp,
+        assigned_approver: None,
+        template_seq,
+        is_voided: false,
+        hash_algo,
+        escalation_count: 0,
+        genesis_hash: genesis_hash(workflow_id, &template_hash, caller, timestamp),
+        finalizes_at: 0,
+    };
+    
+    // Store workflow
+    let workflows_dict = get_workflows_dict();
+    let key = workflow_id.to_string();
+    storage::dictionary_put(workflows_dict, &key, workflow);
+    
+    // Initialize empty transitions list
+    let transitions_dict = get_transitions_dict();
+    let empty_transitions: Vec<TransitionRecord> = Vec::new();
+    storage::dictionary_put(transitions_dict, &key, empty_transitions);
+    
+    update_deadline_index(workflow_id, 0, deadline);
+    move_state_count(None, initial_state);
+    move_state_index(workflow_id, None, initial_state);
+    
+    // Index the workflow under its creator
+    let mut created = read_creator_workflows(caller);
+    created.push(workflow_id);
+    storage::dictionary_put(get_creator_index_dict(), &caller.to_string(), created);
+    
+    emit_event(
+        "workflow_created",
+        &[
+            ("workflow_id", workflow_id.to_string()),
+            ("template_hash", hash_key(&template_hash)),
+            ("creator", caller.to_string()),
+            ("created_at", timestamp.to_string()),
+        ],
+    );
+    emit_transition_event(workflow_id, initial_state, initial_state, caller, timestamp);
+    
+    workflow_id
+}
+
+/// Zero the comment hashes of confidential records unless the caller is an
+/// AUDITOR or ADMIN.
+fn redact_for_caller(transitions: &mut [TransitionRecord]) {
+    redact_confidential(transitions, read_role_mask(runtime::get_caller()));
+}
+
+/// Zero the comment hashes of confidential records unless `reader_mask`
+/// includes AUDITOR or ADMIN.
+fn redact_confidential(transitions: &mut [TransitionRecord], reader_mask: u64) {
+    if reader_mask & (roles::AUDITOR | roles::ADMIN) != 0 {
+        return;
+    }
+    for transition in transitions.iter_mut().filter(|t| t.confidential) {
+        transition.comment_hash = [0u8; 32];
+    }
+}
+
+/// Gather the histories of `workflow_ids` in order within a record budget.
+///
+/// IDs for which `read_history` yields nothing are skipped. A history longer
+/// than the remaining budget is cut short and ends the batch; the result is
+/// flagged as truncated only when records were cut or a non-empty history
+/// was omitted.
+fn collect_histories<F: Fn(U256) -> Option<Vec<TransitionRecord>>>(
+    workflow_ids: Vec<U256>,
+    budget: usize,
+    read_history: F,
+) -> (Vec<(U256, Vec<TransitionRecord>)>, bool) {
+    let mut remaining = budget;
+    let mut histories: Vec<(U256, Vec<TransitionRecord>)> = Vec::new();
+    for workflow_id in workflow_ids {
+        let mut transitions = match read_history(workflow_id) {
+            Some(transitions) => transitions,
+            None => continue,
+        };
+        if transitions.len() > remaining {
+            if remaining > 0 {
+                transitions.truncate(remaining);
+                histories.push((workflow_id, transitions));
+            }
+            return (histories, true);
+        }
+        remaining -= transitions.len();
+        histories.push((workflow_id, transitions));
+    }
+    (histories, false)
+}
+
+/// Get current workflow count.
+fn read_workflow_count() -> U256 {
+    read_counter(get_workflow_count_uref())
+}
+
+/// Increment and return new workflow count.
+///
+/// IDs already taken by a derived ID are skipped, so the returned count is
+/// always free to claim.
+fn increment_workflow_count() -> U256 {
+    // Resolve the named key once for both the read and the write
+    let uref = get_workflow_count_uref();
+    let new_count = next_free_id(read_counter(uref), |id| is_workflow_id_in_use(&id.to_string()))
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::Overflow as u16));
+    
+    storage::write(uref, new_count);
+    new_count
+}
+
+/// The first ID above `count` for which `in_use` is false, or None on
+/// overflow.
+fn next_free_id<F: Fn(U256) -> bool>(count: U256, in_use: F) -> Option<U256> {
+    let mut id = count.checked_add(U256::one())?;
+    while in_use(id) {
+        id = id.checked_add(U256::one())?;
+    }
+    Some(id)
+}
+
+/// Get the workflow count URef.
+fn get_workflow_count_uref() -> URef {
+    runtime::get_key(WORKFLOW_COUNT_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the total transitions URef.
+fn get_total_transitions_uref() -> URef {
+    runtime::get_key(TOTAL_TRANSITIONS_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read a U256 counter, treating an unset value as zero.
+fn read_counter(uref: URef) -> U256 {
+    storage::read(uref)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(U256::zero())
+}
+
+/// Get the number of state transitions committed contract-wide.
+fn read_total_transitions() -> U256 {
+    read_counter(get_total_transitions_uref())
+}
+
+/// Count one more committed state transition.
+fn increment_total_transitions() {
+    let uref = get_total_transitions_uref();
+    let total = read_counter(uref)
+        .checked_add(U256::one())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::Overflow as u16));
+    
+    storage::write(uref, total);
+}
+
+/// Get the transition type counts dictionary URef.
+fn get_transition_type_counts_dict() -> URef {
+    runtime::get_key(TRANSITION_TYPE_COUNTS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read the number of transitions committed into a state contract-wide.
+fn read_transition_type_count(to_state: u8) -> U256 {
+    storage::dictionary_get(get_transition_type_counts_dict(), &to_state.to_string())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(U256::zero())
+}
+
+/// Count one more committed transition into a state.
+fn increment_transition_type_count(to_state: u8) {
+    let count = read_transition_type_count(to_state)
+        .checked_add(U256::one())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::Overflow as u16));
+    storage::dictionary_put(get_transition_type_counts_dict(), &to_state.to_string(), count);
+}
+
+/// Reserve a caller-chosen workflow ID, reverting if it is zero or taken.
+fn claim_workflow_id(workflow_id: U256) {
+    if workflow_id.is_zero() {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    
+    if is_workflow_id_in_use(&workflow_id.to_string()) {
+        runtime::revert(ApiError::User(WorkflowError::WorkflowAlreadyExists as u16));
+    }
+}
+
+/// Check whether an active or archived workflow has the ID.
+fn is_workflow_id_in_use(key: &str) -> bool {
+    // Archived workflows keep their ID
+    let archived: Option<WorkflowData> =
+        storage::dictionary_get(get_workflows_archive_dict(), key)
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    read_workflow(key).is_some() || archived.is_some()
+}
+
+/// Check whether a caller-supplied ID may be raised into the workflow
+/// count: non-zero and at most `MAX_PAGE_SIZE` above the current count, so
+/// the count can never be pushed out of reach of range scans or overflow.
+fn is_countable_id(workflow_id: U256, count: U256) -> bool {
+    !workflow_id.is_zero() && workflow_id <= count.saturating_add(U256::from(MAX_PAGE_SIZE))
+}
+
+/// Raise the workflow count to a caller-supplied ID so the count stays the
+/// highest ID in use and auto-increment IDs never collide with it.
+fn raise_workflow_count(workflow_id: U256) {
+    let uref = get_workflow_count_uref();
+    if workflow_id > read_counter(uref) {
+        storage::write(uref, workflow_id);
+    }
+}
+
+/// Derive a workflow ID from its creation inputs.
+///
+/// blake2b-256 over template_hash (32 bytes) || data_hash (32 bytes) ||
+/// creator account hash (32 bytes) || nonce (8 bytes, little-endian); the
+/// first 16 digest bytes, read big-endian, are the ID. Truncating keeps the
+/// decimal dictionary key within Casper's 64-byte item key limit.
+fn derive_workflow_id(
+    template_hash: &[u8; 32],
+    data_hash: &[u8; 32],
+    creator: AccountHash,
+    nonce: u64,
+) -> U256 {
+    let mut preimage = Vec::with_capacity(104);
+    preimage.extend_from_slice(template_hash);
+    preimage.extend_from_slice(data_hash);
+    preimage.extend_from_slice(creator.as_bytes());
+    preimage.extend_from_slice(&nonce.to_le_bytes());
+    let digest = blake2b(preimage);
+    U256::from_big_endian(&digest[..16])
+}
+
+/// Commit to the fields of a workflow that never change after creation.
+///
+/// blake2b-256 over id (32 bytes, big-endian) || template_hash (32 bytes) ||
+/// creator account hash (32 bytes) || created_at (8 bytes, little-endian).
+fn genesis_hash(
+    workflow_id: U256,
+    template_hash: &[u8; 32],
+    creator: AccountHash,
+    created_at: u64,
+) -> [u8; 32] {
+    let mut id_bytes = [0u8; 32];
+    workflow_id.to_big_endian(&mut id_bytes);
+    
+    let mut preimage = Vec::with_capacity(104);
+    preimage.extend_from_slice(&id_bytes);
+    preimage.extend_from_slice(template_hash);
+    preimage.extend_from_slice(creator.as_bytes());
+    preimage.extend_from_slice(&created_at.to_le_bytes());
+    blake2b(preimage)
+}
+
+/// Check a workflow's genesis_hash against its immutable fields and the
+/// creator it had at creation. False when no commitment was stored.
+fn integrity_holds(workflow: &WorkflowData, original_creator: AccountHash) -> bool {
+    let expected = genesis_hash(
+        workflow.id,
+        &workflow.template_hash,
+        original_creator,
+        workflow.created_at,
+    );
+    workflow.genesis_hash != [0u8; 32] && workflow.genesis_hash == expected
+}
+
+/// Get the template counts dictionary URef.
+fn get_template_counts_dict() -> URef {
+    runtime::get_key(TEMPLATE_COUNTS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the number of workflows created from a template.
+fn read_template_count(template_hash: &[u8; 32]) -> u64 {
+    storage::dictionary_get(get_template_counts_dict(), &hash_key(template_hash))
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(0)
+}
+
+/// Increment and return a template's workflow count.
+fn increment_template_count(template_hash: &[u8; 32]) -> u64 {
+    let count = read_template_count(template_hash)
+        .checked_add(1)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::Overflow as u16));
+    storage::dictionary_put(get_template_counts_dict(), &hash_key(template_hash), count);
+    count
+}
+
+/// Get the state catalog dictionary URef.
+fn get_state_catalog_dict() -> URef {
+    runtime::get_key(STATE_CATALOG_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Look up a custom state's (name hash, is_terminal) catalog entry.
+fn read_catalog_state(state: u8) -> Option<([u8; 32], bool)> {
+    storage::dictionary_get(get_state_catalog_dict(), &state.to_string())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Get the state counts dictionary URef.
+fn get_state_counts_dict() -> URef {
+    runtime::get_key(STATE_COUNTS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read the number of workflows currently in a state.
+fn read_state_count(state: u8) -> U256 {
+    storage::dictionary_get(get_state_counts_dict(), &state.to_string())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(U256::zero())
+}
+
+/// Move one workflow between state tallies. `None` for `from` counts a
+/// newly created workflow.
+fn move_state_count(from: Option<u8>, to: u8) {
+    if from == Some(to) {
+        return;
+    }
+    let counts_dict = get_state_counts_dict();
+    if let Some(from) = from {
+        let count = read_state_count(from).saturating_sub(U256::one());
+        storage::dictionary_put(counts_dict, &from.to_string(), count);
+    }
+    let count = read_state_count(to)
+        .checked_add(U256::one())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::Overflow as u16));
+    storage::dictionary_put(counts_dict, &to.to_string(), count);
+}
+
+/// Get the state index dictionary URef.
+fn get_state_index_dict() -> URef {
+    runtime::get_key(STATE_INDEX_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Read the IDs of the workflows currently in a state, oldest entry first.
+fn read_state_index(state: u8) -> Vec<U256> {
+    storage::dictionary_get(get_state_index_dict(), &state.to_string())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default()
+}
+
+/// Move a workflow between state buckets. `None` for `from` adds a newly
+/// created workflow.
+///
+/// Leaving a bucket scans it, so each move costs O(n) in the size of the
+/// source bucket; states that accumulate many workflows (typically the
+/// terminal ones) make moves out of them more expensive, but terminal
+/// states are rarely left.
+fn move_state_index(workflow_id: U256, from: Option<u8>, to: u8) {
+    if from == Some(to) {
+        return;
+    }
+    let index_dict = get_state_index_dict();
+    let mut source = match from {
+        Some(from) => read_state_index(from),
+        None => Vec::new(),
+    };
+    let mut target = read_state_index(to);
+    move_index_entry(&mut source, &mut target, workflow_id);
+    if let Some(from) = from {
+        storage::dictionary_put(index_dict, &from.to_string(), source);
+    }
+    storage::dictionary_put(index_dict, &to.to_string(), target);
+}
+
+/// Move an ID from one index list to the end of another.
+fn move_index_entry(from: &mut Vec<U256>, to: &mut Vec<U256>, id: U256) {
+    from.retain(|entry| *entry != id);
+    to.push(id);
+}
+
+/// Check if a state is predefined or registered in the state catalog.
+fn is_known_state(state: u8) -> bool {
+    if state >= states::CUSTOM_BASE {
+        return read_catalog_state(state).is_some();
+    }
+    is_builtin_state(state)
+}
+
+/// Check if a state is one of the predefined states.
+fn is_builtin_state(state: u8) -> bool {
+    matches!(
+        state,
+        states::DRAFT
+            | states::CREATED_PENDING
+            | states::PENDING_REVIEW
+            | states::APPROVED
+            | states::REJECTED
+            | states::ESCALATED
+            | states::APPEALED
+            | states::CANCELLED
+    )
+}
+
+/// Check if a state is terminal (workflow complete).
+/// Custom states are terminal only if registered as such in the catalog.
+fn is_terminal_state(state: u8) -> bool {
+    if state >= states::CUSTOM_BASE {
+        return read_catalog_state(state).map(|(_, is_terminal)| is_terminal).unwrap_or(false);
+    }
+    is_builtin_terminal_state(state)
+}
+
+/// Check if a predefined state is terminal.
+fn is_builtin_terminal_state(state: u8) -> bool {
+    matches!(state, states::APPROVED | states::REJECTED | states::CANCELLED)
+}
+
+/// Check if a state completes workflows of a template.
+/// Falls back to the built-in terminal states when the template configures
+/// none.
+fn is_template_terminal_state(template_hash: &[u8; 32], state: u8) -> bool {
+    let terminal: Option<Vec<u8>> =
+        storage::dictionary_get(get_terminal_states_dict(), &hash_key(template_hash))
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    
+    configured_terminal(terminal.as_deref(), state).unwrap_or_else(|| is_terminal_state(state))
+}
+
+/// Whether a template's configured terminal states include `state`, or
+/// None when the template configures none.
+fn configured_terminal(terminal: Option<&[u8]>, state: u8) -> Option<bool> {
+    match terminal {
+        Some(terminal) if !terminal.is_empty() => Some(terminal.contains(&state)),
+        _ => None,
+    }
+}
+
+/// Get the roles permitted to perform a state transition.
+/// This implements the basic state machine logic: the caller must hold at
+/// least one role in the returned mask. Returns None when the transition is
+/// not allowed.
+fn required_role(from: u8, to: u8) -> Option<u64> {
+    const REVIEWERS: u64 = roles::APPROVER | roles::SENIOR_APPROVER;
+    
+    match (from, to) {
+        // From CREATED_PENDING (intake approval, never by the creator)
+        (states::CREATED_PENDING, states::DRAFT) => Some(roles::REVIEWER),
+        
+        // From DRAFT
+        (states::DRAFT, states::PENDING_REVIEW) => Some(roles::REQUESTER),
+        (states::DRAFT, states::CANCELLED) => Some(roles::REQUESTER),
+        
+        // From PENDING_REVIEW (withdrawal to DRAFT goes through
+        // withdraw_to_draft)
+        (states::PENDING_REVIEW, states::APPROVED) => Some(REVIEWERS),
+        (states::PENDING_REVIEW, states::REJECTED) => Some(REVIEWERS),
+        (states::PENDING_REVIEW, states::ESCALATED) => Some(roles::APPROVER),
+        
+        // From ESCALATED
+        (states::ESCALATED, states::APPROVED) => Some(roles::SENIOR_APPROVER),
+        (states::ESCALATED, states::REJECTED) => Some(roles::SENIOR_APPROVER),
+        
+        // From REJECTED (formal appeal, creator only; reopening to DRAFT
+        // goes through reopen_workflow instead)
+        (states::REJECTED, states::APPEALED) => Some(roles::REQUESTER),
+        
+        // From APPEALED (decided by senior approver)
+        (states::APPEALED, states::APPROVED) => Some(roles::SENIOR_APPROVER),
+        (states::APPEALED, states::REJECTED) => Some(roles::SENIOR_APPROVER),
+        
+        // No other transitions allowed
+        _ => None,
+    }
+}
+
+/// Get the role a transition demands regardless of template rules.
+/// Escalation is raised by approvers and only settled by senior approvers.
+fn mandatory_role(from: u8, to: u8) -> Option<u64> {
+    match (from, to) {
+        (states::PENDING_REVIEW, states::ESCALATED) => Some(roles::APPROVER),
+        (states::ESCALATED, states::APPROVED) => Some(roles::SENIOR_APPROVER),
+        (states::ESCALATED, states::REJECTED) => Some(roles::SENIOR_APPROVER),
+        _ => None,
+    }
+}
+
+/// Get the roles permitted to perform a transition under a template's rules.
+/// Falls back to the built-in state machine when the template defines none.
+/// Template masks are narrowed to the transition's mandatory role, if any.
+fn template_required_role(template_hash: &[u8; 32], from: u8, to: u8) -> Option<u64> {
+    let rules: Option<Vec<TransitionRule>> =
+        storage::dictionary_get(get_transition_rules_dict(), &hash_key(template_hash))
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    
+    match rules {
+        Some(rules) => rules
+            .iter()
+            .find(|(rule_from, rule_to, _)| *rule_from == from && *rule_to == to)
+            .map(|(_, _, role_mask)| match mandatory_role(from, to) {
+                Some(mandatory) => role_mask & mandatory,
+                None => *role_mask,
+            }),
+        None => required_role(from, to),
+    }
+}
+
+/// Check if a transition is a formal appeal of a rejection.
+/// Appeals are the only transitions allowed out of a completed workflow.
+fn is_appeal_transition(from: u8, to: u8) -> bool {
+    from == states::REJECTED && to == states::APPEALED
+}
+
+/// Throttle rapid toggling that would bloat the history: every entry point
+/// committing a transition for a caller requires the template's minimum
+/// interval to have passed since the workflow's last update. Admin
+/// overrides are exempt.
+fn check_min_interval(workflow: &WorkflowData, now: u64) -> Result<(), WorkflowError> {
+    let interval = read_min_transition_interval(&workflow.template_hash);
+    if is_too_soon(workflow.updated_at, now, interval) {
+        return Err(WorkflowError::TransitionTooSoon);
+    }
+    Ok(())
+}
+
+/// Check whether `now` (ms) falls within `interval_seconds` of `updated_at`.
+fn is_too_soon(updated_at: u64, now: u64, interval_seconds: u64) -> bool {
+    now.saturating_sub(updated_at) < interval_seconds.saturating_mul(1000)
+}
+
+/// Check a caller's role mask against the roles a transition requires.
+/// A claimed role must not exceed what the caller actually holds.
+fn check_role_mask(
+    actor_role: u64,
+    required: u64,
+    claimed_role: Option<u64>,
+) -> Result<(), WorkflowError> {
+    if actor_role & required == 0 {
+        return Err(WorkflowError::InsufficientPermissions);
+    }
+    match claimed_role {
+        Some(claimed) if claimed & !actor_role != 0 => Err(WorkflowError::InsufficientPermissions),
+        _ => Ok(()),
+    }
+}
+
+/// Apply the state machine and permission checks of `transition_state`
+/// for a caller without mutating anything.
+///
+/// Returns the caller's effective role mask, or the error the transition
+/// would revert with.
+fn check_transition(
+    workflow: &WorkflowData,
+    to_state: u8,
+    caller: AccountHash,
+    claimed_role: Option<u64>,
+) -> Result<u64, WorkflowError> {
+    let required = check_state_machine(workflow, to_state, get_block_time())?;
+    
+    // Check the caller's registered or delegated roles cover the transition
+    let actor_role = effective_role_mask(caller);
+    check_role_mask(actor_role, required, claimed_role)?;
+    
+    check_template_duties(workflow, to_state, caller)?;
+    
+    Ok(actor_role)
+}
+
+/// Apply the checks of `check_transition` to an SLA-driven escalation of an
+/// overdue workflow, which needs no role: the caller only triggers it.
+///
+/// Returns the actor_role to record, the caller's roles with
+/// `roles::SYSTEM_FLAG` set, or the error the escalation would revert with.
+fn check_overdue_transition(
+    workflow: &WorkflowData,
+    to_state: u8,
+    caller: AccountHash,
+) -> Result<u64, WorkflowError> {
+    let now = get_block_time();
+    check_state_machine(workflow, to_state, now)?;
+    
+    // The SLA only ever escalates
+    if to_state != states::ESCALATED {
+        return Err(WorkflowError::InvalidTransition);
+    }
+    if !is_overdue(workflow, now) {
+        return Err(WorkflowError::NotOverdue);
+    }
+    
+    check_template_duties(workflow, to_state, caller)?;
+    
+    Ok(read_role_mask(caller) | roles::SYSTEM_FLAG)
+}
+
+/// Check a transition against the state machine and the template's rules,
+/// independent of who performs it. Returns the roles the rule requires.
+fn check_state_machine(
+    workflow: &WorkflowData,
+    to_state: u8,
+    now: u64,
+) -> Result<u64, WorkflowError> {
+    let from_state = workflow.current_state;
+    
+    if workflow.is_voided {
+        return Err(WorkflowError::WorkflowVoided);
+    }
+    
+    if !is_known_state(to_state) {
+        return Err(WorkflowError::InvalidArgument);
+    }
+    
+    // Check workflow is not completed (a rejection may still be appealed);
+    // an expired grace period counts as completed ahead of `finalize`
+    let is_closed = workflow.is_completed || is_grace_over(workflow, now);
+    if is_closed && !is_appeal_transition(from_state, to_state) {
+        return Err(WorkflowError::WorkflowAlreadyCompleted);
+    }
+    
+    check_min_interval(workflow, now)?;
+    
+    // Validate transition against the template's rules
+    template_required_role(&workflow.template_hash, from_state, to_state)
+        .ok_or(WorkflowError::InvalidTransition)
+}
+
+/// Check `check_caller_duties` against the workflow template's settings.
+fn check_template_duties(
+    workflow: &WorkflowData,
+    to_state: u8,
+    caller: AccountHash,
+) -> Result<(), WorkflowError> {
+    check_caller_duties(
+        workflow,
+        to_state,
+        caller,
+        || is_template_approver(&workflow.template_hash, caller),
+        || is_self_approval_allowed(&workflow.template_hash),
+        read_max_escalations,
+    )
+}
+
+/// Check the comment and condition a transition into `to_state` supplies
+/// against what the workflow's template requires for it.
+fn check_transition_inputs(
+    workflow: &WorkflowData,
+    to_state: u8,
+    comment_hash: &[u8; 32],
+    condition_hash: Option<[u8; 32]>,
+) -> Result<(), WorkflowError> {
+    check_comment_required(&workflow.template_hash, to_state, comment_hash)?;
+    check_rule_condition(&workflow.template_hash, workflow.current_state, to_state, condition_hash)
+}
+
+/// Check the rules of a transition that depend on who the caller is rather
+/// than on the roles it holds. The template settings are read through
+/// `in_pool`, `self_approval_allowed` and `max_escalations`, only when a
+/// rule needs them.
+fn check_caller_duties<P, S, M>(
+    workflow: &WorkflowData,
+    to_state: u8,
+    caller: AccountHash,
+    in_pool: P,
+    self_approval_allowed: S,
+    max_escalations: M,
+) -> Result<(), WorkflowError>
+where
+    P: Fn() -> bool,
+    S: Fn() -> bool,
+    M: Fn() -> u8,
+{
+    let from_state = workflow.current_state;
+    
+    // Only the creator may appeal their own rejected workflow
+    if is_appeal_transition(from_state, to_state) && caller != workflow.creator {
+        return Err(WorkflowError::InsufficientPermissions);
+    }
+    
+    // Intake must be approved by someone other than the creator
+    if from_state == states::CREATED_PENDING && caller == workflow.creator {
+        return Err(WorkflowError::InsufficientPermissions);
+    }
+    
+    // Review decisions are routed to the template's approver pool
+    let is_review = from_state == states::PENDING_REVIEW || from_state == states::ESCALATED;
+    let is_decision = to_state == states::APPROVED || to_state == states::REJECTED;
+    if is_review && is_decision && !in_pool() {
+        return Err(WorkflowError::InsufficientPermissions);
+    }
+    
+    if to_state == states::ESCALATED && workflow.escalation_count >= max_escalations() {
+        return Err(WorkflowError::MaxEscalationReached);
+    }
+    
+    // Segregation of duties: creators do not approve their own workflows
+    if to_state == states::APPROVED && caller == workflow.creator && !self_approval_allowed() {
+        return Err(WorkflowError::SelfApprovalForbidden);
+    }
+    
+    Ok(())
+}
+
+/// Apply one transition of a `transition_batch`, returning the error that
+/// `transition_state` would have reverted with instead of reverting.
+///
+/// `transition` carries the fields shared by the batch; its from_state and
+/// actor_role are filled in per workflow.
+fn apply_batch_transition(
+    workflow_id: U256,
+    claimed_role: Option<u64>,
+    mut transition: TransitionRecord,
+) -> Result<(), WorkflowError> {
+    let key = workflow_id.to_string();
+    let mut workflow = read_workflow(&key).ok_or(WorkflowError::WorkflowNotFound)?;
+    let from_state = workflow.current_state;
+    let to_state = transition.to_state;
+    let caller = transition.actor;
+    
+    let actor_role = check_transition(&workflow, to_state, caller, claimed_role)?;
+    if transition.timestamp < workflow.updated_at {
+        return Err(WorkflowError::TimestampRegression);
+    }
+    
+    // Batches carry no condition hash or category
+    check_transition_inputs(&workflow, to_state, &transition.comment_hash, None)?;
+    if is_template_terminal_state(&workflow.template_hash, to_state) && read_category_required() {
+        return Err(WorkflowError::MissingArgument);
+    }
+    
+    let quorum_vote = from_state == states::PENDING_REVIEW && to_state == states::APPROVED;
+    if quorum_vote && has_approved(&key, caller) {
+        return Err(WorkflowError::DuplicateApproval);
+    }
+    
+    // Only lock once validated, as skipped entries return without unlocking
+    lock_workflow(&key);
+    
+    // Quorum approvals only commit once enough distinct approvers voted
+    if quorum_vote && !record_approval(&key, caller, workflow.required_approvals) {
+        unlock_workflow(&key);
+        return Ok(());
+    }
+    
+    transition.from_state = from_state;
+    transition.actor_role = actor_role;
+    
+    commit_transition(&key, &mut workflow, transition);
+    unlock_workflow(&key);
+    Ok(())
+}
+
+/// Check a transition's decision category: a supplied category must pass
+/// `is_registered`, and one must be supplied when `required` holds.
+fn check_category<R, Q>(
+    category_id: Option<u32>,
+    is_registered: R,
+    required: Q,
+) -> Result<(), WorkflowError>
+where
+    R: Fn(u32) -> bool,
+    Q: Fn() -> bool,
+{
+    match category_id {
+        Some(id) if !is_registered(id) => Err(WorkflowError::InvalidArgument),
+        None if required() => Err(WorkflowError::MissingArgument),
+        _ => Ok(()),
+    }
+}
+
+/// Validate and apply a transition of a workflow to `to_state` for the
+/// caller, reading the remaining `transition_state` arguments. Returns the
+/// workflow's state afterwards.
+///
+/// `expected_from`, if given, is the state the workflow must be leaving;
+/// any other state reverts with `InvalidTransition`.
+fn apply_transition(workflow_id: U256, to_state: u8, expected_from: Option<u8>) -> u8 {
+    let claimed_role: Option<u64> = runtime::try_get_named_arg("actor_role");
+    let comment_hash: [u8; 32] = runtime::get_named_arg("comment_hash");
+    let category_id: Option<u32> = runtime::try_get_named_arg("category_id");
+    let request_id: Option<[u8; 32]> = runtime::try_get_named_arg("request_id");
+    let external_ref: [u8; 32] = runtime::try_get_named_arg("external_ref").unwrap_or([0u8; 32]);
+    let confidential: bool = runtime::try_get_named_arg("confidential").unwrap_or(false);
+    let comment: Option<String> = runtime::try_get_named_arg("comment");
+    let condition_hash: Option<[u8; 32]> = runtime::try_get_named_arg("condition_hash");
+    let reason_code = read_reason_code_arg();
+    let hash_algo = read_hash_algo_arg(hash_algos::KECCAK256);
+    
+    // Stored comments are checked against comment_hash; dictionaries are
+    // publicly readable, so confidential records never carry the text
+    if let Some(comment) = &comment {
+        if confidential {
+            runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+        }
+        validate_comment(comment, &comment_hash, hash_algo);
+    }
+    
+    // Get caller and timestamp
+    let caller = runtime::get_caller();
+    let timestamp = get_block_time();
+    
+    // A resubmitted request has already been applied
+    let request_key = request_id.map(|id| request_key(workflow_id, &id));
+    if let Some(request_key) = &request_key {
+        let seen: Option<bool> = storage::dictionary_get(get_seen_requests_dict(), request_key)
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+        if seen.is_some() {
+            return load_workflow(&workflow_id.to_string()).current_state;
+        }
+    }
+    
+    // Load workflow
+    let key = workflow_id.to_string();
+    
+    let mut workflow = load_workflow(&key);
+    
+    // Guard the read-modify-write below against nested calls; reverting
+    // rolls the lock back with everything else
+    lock_workflow(&key);
+    
+    // Validate the transition and the caller's authority for it
+    let from_state = workflow.current_state;
+    if expected_from.is_some_and(|expected| expected != from_state) {
+        runtime::revert(ApiError::User(WorkflowError::InvalidTransition as u16));
+    }
+    let actor_role = check_transition(&workflow, to_state, caller, claimed_role).unwrap_or_revert();
+    check_transition_inputs(&workflow, to_state, &comment_hash, condition_hash).unwrap_or_revert();
+    
+    // Validate decision category
+    check_category(category_id, is_registered_category, || {
+        is_template_terminal_state(&workflow.template_hash, to_state) && read_category_required()
+    })
+    .unwrap_or_revert();
+    
+    // Remember the request so a retry is a no-op
+    if let Some(request_key) = &request_key {
+        storage::dictionary_put(get_seen_requests_dict(), request_key, true);
+    }
+    
+    // Quorum approvals only commit once enough distinct approvers voted
+    if from_state == states::PENDING_REVIEW
+        && to_state == states::APPROVED
+        && !record_approval(&key, caller, workflow.required_approvals)
+    {
+        unlock_workflow(&key);
+        return from_state;
+    }
+    
+    // Create transition record
+    let transition = TransitionRecord {
+        from_state,
+        to_state,
+        actor: caller,
+        actor_role,
+        timestamp,
+        comment_hash,
+        category_id: category_id.unwrap_or(0),
+        prev_hash: [0u8; 32],
+        external_ref,
+        confidential,
+        reason_code,
+        hash_algo,
+        block_height: 0,
+    };
+    
+    let index = commit_transition(&key, &mut workflow, transition);
+    if let Some(comment) = comment {
+        storage::dictionary_put(get_comments_dict(), &comment_key(workflow_id, index), comment);
+    }
+    
+    unlock_workflow(&key);
+    workflow.current_state
+}
+
+/// Read the optional `hash_algo` argument, reverting unless it names an
+/// algorithm in `hash_algos`.
+fn read_hash_algo_arg(default: u8) -> u8 {
+    let hash_algo: u8 = runtime::try_get_named_arg("hash_algo").unwrap_or(default);
+    check_hash_algo(hash_algo).unwrap_or_revert();
+    hash_algo
+}
+
+/// Check that a hash algorithm ID is in `hash_algos`.
+fn check_hash_algo(hash_algo: u8) -> Result<(), WorkflowError> {
+    if hash_algo > hash_algos::SHA256 {
+        return Err(WorkflowError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Read the optional `reason_code` argument (0 when absent).
+/// Passed as u32, since u16 has no CLType, and range checked.
+fn read_reason_code_arg() -> u16 {
+    let reason_code: u32 = runtime::try_get_named_arg("reason_code").unwrap_or(0);
+    u16::try_from(reason_code)
+        .ok()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::InvalidArgument as u16))
+}
+
+/// Get current block timestamp.
+/// Note: In Casper, we use the blocktime from runtime.
+fn get_block_time() -> u64 {
+    runtime::get_blocktime().into()
+}
+
+/// Return a value from the current entry point, reverting with
+/// `SerializationError` if it cannot be encoded.
+fn return_value<T: CLTyped + ToBytes>(value: T) -> ! {
+    let value = CLValue::from_t(value)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::SerializationError as u16));
+    runtime::ret(value)
+}
+
+/// Get the configured events topic, or None when events are disabled.
+fn read_events_topic() -> Option<String> {
+    let uref = runtime::get_key(EVENTS_TOPIC_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    storage::read(uref)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .flatten()
+}
+
+/// Emit an event on the configured events topic; a no-op when events are
+/// disabled. Fields are written in the given order as
+/// `<event>;<field>=<value>;...`.
+fn emit_event(event: &str, fields: &[(&str, String)]) {
+    let topic = match read_events_topic() {
+        Some(topic) => topic,
+        None => return,
+    };
+    let mut payload = String::from(event);
+    for (name, value) in fields {
+        payload.push_str(&format!(";{}={}", name, value));
+    }
+    runtime::emit_message(&topic, &MessagePayload::String(payload))
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+}
+
+/// Emit the `transition` event for a workflow state change.
+fn emit_transition_event(
+    workflow_id: U256,
+    from_state: u8,
+    to_state: u8,
+    actor: AccountHash,
+    timestamp: u64,
+) {
+    emit_event(
+        "transition",
+        &[
+            ("workflow_id", workflow_id.to_string()),
+            ("from_state", from_state.to_string()),
+            ("to_state", to_state.to_string()),
+            ("actor", actor.to_string()),
+            ("timestamp", timestamp.to_string()),
+        ],
+    );
+}
+
+// =============================================================================
+// Entry Points
+// =============================================================================
+
+/// Create a new workflow instance.
+///
+/// The workflow starts in DRAFT, or in CREATED_PENDING awaiting a
+/// reviewer's intake approval when that flag is enabled.
+///
+/// # Arguments
+///
+/// * `template_hash` - 32-byte hash of the workflow template definition
+/// * `data_hash` - 32-byte hash of the associated business data
+/// * `hash_algo` - Optional algorithm that produced both hashes (see
+///   `hash_algos`; default keccak256)
+/// * `sla_seconds` - Optional time allowed in the initial state; defaults
+///   to the template's SLA for that state
+/// * `required_approvals` - Optional number of distinct approvers needed
+///   to approve from PENDING_REVIEW (default 1)
+/// * `use_derived_id` - Optional flag deriving the ID from the inputs and
+///   `nonce` instead of the counter (default false), so it can be
+///   precomputed off-chain; see `derive_workflow_id` for the scheme.
+///   Derived IDs do not move the workflow count, so everything that scans
+///   1..=workflow_count misses them: `get_workflows_range`,
+///   `get_overdue_workflows`, `reconcile_count` and the global Merkle root.
+///   Track derived workflows by ID, or through `get_workflows_in_state`
+///   and `get_workflows_by_creator`
+/// * `nonce` - Caller-chosen nonce for the derived ID; required with
+///   `use_derived_id`
+///
+/// # Returns
+///
+/// The new workflow ID (U256)
+///
+/// # Events
+///
+/// `workflow_created`, then `transition` from and to the initial state
+/// marking the creation.
+///
+/// # Errors
+///
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+/// * `InsufficientPermissions` - Creation is restricted and the caller
+///   lacks REQUESTER
+/// * `InvalidArgument` - `required_approvals` is zero or `hash_algo` unknown
+/// * `MissingArgument` - `use_derived_id` set without a `nonce`
+/// * `WorkflowAlreadyExists` - The derived ID is already in use
+#[no_mangle]
+pub extern "C" fn create_workflow() {
+    require_not_paused();
+    consume_call_budget();
+    
+    // Get arguments
+    let template_hash: [u8; 32] = runtime::get_named_arg("template_hash");
+    let data_hash: [u8; 32] = runtime::get_named_arg("data_hash");
+    let hash_algo = read_hash_algo_arg(hash_algos::KECCAK256);
+    let sla_seconds: Option<u64> = runtime::try_get_named_arg("sla_seconds");
+    let required_approvals: u8 = runtime::try_get_named_arg("required_approvals").unwrap_or(1);
+    let use_derived_id: bool = runtime::try_get_named_arg("use_derived_id").unwrap_or(false);
+    let nonce: Option<u64> = runtime::try_get_named_arg("nonce");
+    
+    // A quorum of zero approvers is meaningless
+    if required_approvals == 0 {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    
+    let derived_id = if use_derived_id {
+        let nonce =
+            nonce.unwrap_or_revert_with(ApiError::User(WorkflowError::MissingArgument as u16));
+        Some(derive_workflow_id(&template_hash, &data_hash, runtime::get_caller(), nonce))
+    } else {
+        None
+    };
+    
+    let workflow_id = store_new_workflow(
+        derived_id,
+        template_hash,
+        data_hash,
+        hash_algo,
+        sla_seconds,
+        required_approvals,
+        None,
+    );
+    
+    // Return the new workflow ID
+    return_value(workflow_id);
+}
+
+/// Create a new workflow instance under a caller-supplied ID.
+///
+/// Lets off-chain systems reuse their own record IDs so a deploy retried
+/// after an unclear outcome cannot create a duplicate. Otherwise behaves
+/// like `create_workflow`, but only for callers holding REQUESTER; the
+/// workflow count is raised to the ID if it is higher, so auto-increment
+/// IDs continue above it. The ID may be at most 100 above the current
+/// count, so IDs are reserved in order rather than far ahead.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The ID for the new workflow (non-zero, at most the
+///   workflow count plus 100)
+/// * `template_hash` - 32-byte hash of the workflow template definition
+/// * `data_hash` - 32-byte hash of the associated business data
+/// * `hash_algo` - Optional algorithm that produced both hashes (see
+///   `hash_algos`; default keccak256)
+/// * `sla_seconds` - Optional time allowed in the initial state; defaults
+///   to the template's SLA for that state
+/// * `required_approvals` - Optional number of distinct approvers needed
+///   to approve from PENDING_REVIEW (default 1)
+///
+/// # Returns
+///
+/// The workflow ID (U256)
+///
+/// # Events
+///
+/// `workflow_created`, then `transition` from and to the initial state
+/// marking the creation.
+///
+/// # Errors
+///
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+/// * `InsufficientPermissions` - Caller lacks REQUESTER
+/// * `InvalidArgument` - `workflow_id` is zero or too far above the count,
+///   `required_approvals` is zero, or `hash_algo` unknown
+/// * `WorkflowAlreadyExists` - A workflow (active or archived) has the ID
+#[no_mangle]
+pub extern "C" fn create_workflow_with_id() {
+    require_not_paused();
+    consume_call_budget();
+    
+    // Get arguments
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let template_hash: [u8; 32] = runtime::get_named_arg("template_hash");
+    let data_hash: [u8; 32] = runtime::get_named_arg("data_hash");
+    let hash_algo = read_hash_algo_arg(hash_algos::KECCAK256);
+    let sla_seconds: Option<u64> = runtime::try_get_named_arg("sla_seconds");
+    let required_approvals: u8 = runtime::try_get_named_arg("required_approvals").unwrap_or(1);
+    
+    // A quorum of zero approvers is meaningless
+    if required_approvals == 0 {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    
+    // Chosen IDs move the shared count, so only requesters may pick them
+    if effective_role_mask(runtime::get_caller()) & roles::REQUESTER == 0 {
+        runtime::revert(ApiError::User(WorkflowError::InsufficientPermissions as u16));
+    }
+    if !is_countable_id(workflow_id, read_workflow_count()) {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    
+    let workflow_id = store_new_workflow(
+        Some(workflow_id),
+        template_hash,
+        data_hash,
+        hash_algo,
+        sla_seconds,
+        required_approvals,
+        None,
+    );
+    raise_workflow_count(workflow_id);
+    
+    return_value(workflow_id);
+}
+
+/// Check that a workflow may be imported into `state` with a quorum of
+/// `required_approvals`, using `is_known` to look the state up.
+fn check_import_state<F: Fn(u8) -> bool>(
+    state: u8,
+    required_approvals: u8,
+    is_known: F,
+) -> Result<(), WorkflowError> {
+    // ESCALATED needs an assignee, which an import cannot supply
+    if !is_known(state) || state == states::ESCALATED || required_approvals == 0 {
+        return Err(WorkflowError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Import a workflow migrated from a legacy system directly into a state,
+/// instead of starting it in DRAFT.
+///
+/// A terminal state completes the workflow on import. The history is
+/// seeded with one record from and to the imported state, carrying the
+/// legacy reference, so the import itself is auditable; the record is
+/// counted like any committed transition into that state. Otherwise behaves
+/// like `create_workflow`, with the admin as creator (see
+/// `reassign_creator` to hand the workflow to its owner).
+///
+/// # Arguments
+///
+/// * `template_hash` - 32-byte hash of the workflow template definition
+/// * `data_hash` - 32-byte hash of the associated business data
+/// * `state` - State to import into; must be in the catalog and not
+///   ESCALATED, which needs an assignee (import into PENDING_REVIEW and
+///   use `escalate_to`)
+/// * `external_ref` - Optional record ID in the legacy system (default zero)
+/// * `hash_algo` - Optional algorithm that produced both hashes (see
+///   `hash_algos`; default keccak256)
+/// * `sla_seconds` - Optional time allowed in the imported state; defaults
+///   to the template's SLA for that state
+/// * `required_approvals` - Optional number of distinct approvers needed
+///   to approve from PENDING_REVIEW (default 1)
+///
+/// # Returns
+///
 /// The new workflow ID (U256)
 ///
-/// # Events
+/// # Events
+///
+/// `workflow_created`, then `transition` from and to the imported state,
+/// followed by `workflow_completed` when that state is terminal.
+///
+/// # Errors
+///
+/// * `ContractPaused` - Contract is paused
+/// * `InsufficientPermissions` - Caller is not the admin
+/// * `InvalidArgument` - `state` unknown or ESCALATED,
+///   `required_approvals` zero, or `hash_algo` unknown
+#[no_mangle]
+pub extern "C" fn create_workflow_in_state() {
+    require_not_paused();
+    
+    let template_hash: [u8; 32] = runtime::get_named_arg("template_hash");
+    let data_hash: [u8; 32] = runtime::get_named_arg("data_hash");
+    let state: u8 = runtime::get_named_arg("state");
+    let external_ref: [u8; 32] = runtime::try_get_named_arg("external_ref").unwrap_or([0u8; 32]);
+    let hash_algo = read_hash_algo_arg(hash_algos::KECCAK256);
+    let sla_seconds: Option<u64> = runtime::try_get_named_arg("sla_seconds");
+    let required_approvals: u8 = runtime::try_get_named_arg("required_approvals").unwrap_or(1);
+    require_admin();
+    
+    check_import_state(state, required_approvals, is_known_state).unwrap_or_revert();
+    
+    let workflow_id = store_new_workflow(
+        None,
+        template_hash,
+        data_hash,
+        hash_algo,
+        sla_seconds,
+        required_approvals,
+        Some(state),
+    );
+    
+    // Seed the history with the import
+    let caller = runtime::get_caller();
+    let timestamp = get_block_time();
+    append_transition(
+        &workflow_id.to_string(),
+        TransitionRecord {
+            from_state: state,
+            to_state: state,
+            actor: caller,
+            actor_role: read_role_mask(caller),
+            timestamp,
+            comment_hash: [0u8; 32],
+            category_id: 0,
+            prev_hash: [0u8; 32],
+            external_ref,
+            confidential: false,
+            reason_code: 0,
+            hash_algo,
+            block_height: 0,
+        },
+    );
+    record_audit_entry(audit_kinds::TRANSITION, workflow_id);
+    increment_total_transitions();
+    increment_transition_type_count(state);
+    
+    if is_template_terminal_state(&template_hash, state) {
+        emit_event(
+            "workflow_completed",
+            &[
+                ("workflow_id", workflow_id.to_string()),
+                ("final_state", state.to_string()),
+                ("timestamp", timestamp.to_string()),
+            ],
+        );
+    }
+    
+    return_value(workflow_id);
+}
+
+/// Create many workflow instances in one call.
+///
+/// Each (template_hash, data_hash) pair creates one workflow exactly as
+/// `create_workflow` would with default SLA and a single required approval.
+/// Any failure reverts the whole batch.
+///
+/// # Arguments
+///
+/// * `template_hashes` - Template hash for each workflow
+/// * `data_hashes` - Data hash for each workflow, parallel to `template_hashes`
+/// * `hash_algo` - Optional algorithm that produced all hashes (see
+///   `hash_algos`; default keccak256)
+///
+/// # Returns
+///
+/// Vector of the new workflow IDs, in input order
+///
+/// # Events
+///
+/// `workflow_created` and a creation `transition` for each workflow.
+///
+/// # Errors
+///
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+/// * `InsufficientPermissions` - Creation is restricted and the caller
+///   lacks REQUESTER
+/// * `InvalidArgument` - The two lists differ in length or `hash_algo` is
+///   unknown
+/// * `Overflow` - Workflow counter overflow
+#[no_mangle]
+pub extern "C" fn create_workflows_batch() {
+    require_not_paused();
+    consume_call_budget();
+    
+    let template_hashes: Vec<[u8; 32]> = runtime::get_named_arg("template_hashes");
+    let data_hashes: Vec<[u8; 32]> = runtime::get_named_arg("data_hashes");
+    let hash_algo = read_hash_algo_arg(hash_algos::KECCAK256);
+    
+    if template_hashes.len() != data_hashes.len() {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    
+    let workflow_ids: Vec<U256> = template_hashes
+        .into_iter()
+        .zip(data_hashes)
+        .map(|(template_hash, data_hash)| {
+            store_new_workflow(None, template_hash, data_hash, hash_algo, None, 1, None)
+        })
+        .collect();
+    
+    return_value(workflow_ids);
+}
+
+/// Execute a state transition on a workflow.
+///
+/// For workflows requiring more than one approval, a PENDING_REVIEW ->
+/// APPROVED request counts as the caller's vote (see `cast_approval`) and
+/// only commits once the quorum is reached.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to transition
+/// * `to_state` - The target state
+/// * `actor_role` - Optional claimed role mask, cross-checked against the
+///   role registry
+/// * `comment_hash` - Hash of any comments/justification
+/// * `category_id` - Optional registered decision category; required for
+///   terminal transitions when the category-required flag is set
+/// * `request_id` - Optional client-chosen ID making the call retryable: a
+///   repeated request_id for the same workflow is a no-op
+/// * `external_ref` - Optional approval ID in an external system of record
+/// * `confidential` - Optional flag hiding comment_hash from readers other
+///   than AUDITOR/ADMIN (default false). This only controls what the
+///   contract's entry points return: the underlying dictionaries remain
+///   readable by anyone querying global state
+/// * `comment` - Optional comment text stored on-chain with the record; its
+///   hash under hash_algo (keccak256 or blake2b) must equal comment_hash.
+///   Not accepted for confidential records
+/// * `reason_code` - Optional reason for the decision, e.g. why a workflow
+///   was rejected (default 0 = none; must fit in u16)
+/// * `hash_algo` - Optional algorithm that produced comment_hash (see
+///   `hash_algos`; default keccak256)
+/// * `condition_hash` - Optional proof of an off-chain condition; must equal
+///   the expected condition when the template rule specifies one
+///
+/// # Returns
+///
+/// The workflow's state after the call: the new state, or PENDING_REVIEW
+/// when the call only recorded a quorum vote (or the current state for a
+/// repeated request_id)
+///
+/// # Events
+///
+/// `transition` with the previous and new state, followed by
+/// `workflow_completed` when the new state is terminal.
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow does not exist
+/// * `WorkflowVoided` - Workflow has been voided
+/// * `InvalidTransition` - Transition not allowed
+/// * `WorkflowAlreadyCompleted` - Workflow in terminal state
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+/// * `InsufficientPermissions` - Caller lacks (directly or by delegation) the
+///   role required for the transition, claims a role it does not hold, appeals a workflow it did
+///   not create, approves intake of its own workflow, or decides a review
+///   while outside the template's approver pool
+/// * `MissingArgument` - Category required but not supplied
+/// * `CommentRequired` - The template requires a comment for the target
+///   state and comment_hash is all zeros
+/// * `ConditionNotMet` - The template rule expects a condition and
+///   condition_hash is missing or differs
+/// * `InvalidArgument` - Unknown target state, category not registered,
+///   comment too long or not matching comment_hash under hash_algo (SHA-256
+///   comments cannot be verified), comment supplied for a confidential
+///   record, reason code out of range, or hash_algo unknown
+/// * `DuplicateApproval` - Caller has already voted towards the quorum
+/// * `SelfApprovalForbidden` - Caller created the workflow it would approve
+/// * `MaxEscalationReached` - Escalating a workflow already escalated the
+///   maximum number of times
+/// * `TransitionTooSoon` - The template's minimum interval since the last
+///   update has not passed
+/// * `HistoryLimitExceeded` - Workflow history is full
+/// * `TimestampRegression` - Block time precedes the last update
+/// * `WorkflowLocked` - A nested call is already transitioning the workflow
+#[no_mangle]
+pub extern "C" fn transition_state() {
+    require_not_paused();
+    consume_call_budget();
+    
+    // Get arguments
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let to_state: u8 = runtime::get_named_arg("to_state");
+    
+    return_value(apply_transition(workflow_id, to_state, None));
+}
+
+/// Apply the same transition to many workflows in one call.
+///
+/// Each workflow is validated individually with the checks of
+/// `transition_state`. By default an invalid entry is skipped and reported
+/// so one bad ID does not block the rest; with `atomic` set, the first
+/// invalid entry reverts the whole batch. A full history always reverts
+/// the batch.
+///
+/// # Arguments
+///
+/// * `workflow_ids` - Workflows to transition (at most 100)
+/// * `to_state` - The target state for every workflow
+/// * `actor_role` - Optional claimed role mask
+/// * `comment_hash` - Hash of any comments/justification, shared by all
+/// * `atomic` - Optional flag reverting on the first failure (default false)
+/// * `reason_code` - Optional decision reason shared by all (default 0)
+/// * `hash_algo` - Optional algorithm that produced comment_hash (see
+///   `hash_algos`; default keccak256)
+///
+/// # Returns
+///
+/// (workflow_id, code) per input in order: 0 when applied (or, under a
+/// quorum, when the vote was recorded), otherwise the `WorkflowError` code
+/// as u32. Terminal transitions fail with `MissingArgument` while the
+/// category-required flag is set, as batches carry no category, and
+/// transitions gated by a rule condition fail with `ConditionNotMet`.
+///
+/// # Events
+///
+/// `transition` for each applied transition, and `workflow_completed` for
+/// each one reaching a terminal state.
+///
+/// # Errors
+///
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+/// * `InvalidArgument` - More than 100 workflow IDs, reason code out of
+///   range, or hash_algo unknown
+/// * `HistoryLimitExceeded` - A workflow history is full
+/// * `WorkflowLocked` - A nested call is already transitioning a workflow
+/// * Any per-workflow error when `atomic` is set
+#[no_mangle]
+pub extern "C" fn transition_batch() {
+    require_not_paused();
+    consume_call_budget();
+    
+    let workflow_ids: Vec<U256> = runtime::get_named_arg("workflow_ids");
+    let to_state: u8 = runtime::get_named_arg("to_state");
+    let claimed_role: Option<u64> = runtime::try_get_named_arg("actor_role");
+    let comment_hash: [u8; 32] = runtime::get_named_arg("comment_hash");
+    let atomic: bool = runtime::try_get_named_arg("atomic").unwrap_or(false);
+    let reason_code = read_reason_code_arg();
+    let hash_algo = read_hash_algo_arg(hash_algos::KECCAK256);
+    
+    if workflow_ids.len() as u64 > MAX_PAGE_SIZE {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    
+    // Shared record; from_state and actor_role are set per workflow
+    let transition = TransitionRecord {
+        from_state: 0,
+        to_state,
+        actor: runtime::get_caller(),
+        actor_role: 0,
+        timestamp: get_block_time(),
+        comment_hash,
+        category_id: 0,
+        prev_hash: [0u8; 32],
+        external_ref: [0u8; 32],
+        confidential: false,
+        reason_code,
+        hash_algo,
+        block_height: 0,
+    };
+    
+    let mut results: Vec<(U256, u32)> = Vec::with_capacity(workflow_ids.len());
+    for workflow_id in workflow_ids {
+        let outcome = apply_batch_transition(workflow_id, claimed_role, transition.clone());
+        let code = match outcome {
+            Ok(()) => 0,
+            Err(error) if atomic => runtime::revert(ApiError::from(error)),
+            Err(error) => error as u32,
+        };
+        results.push((workflow_id, code));
+    }
+    
+    return_value(results);
+}
+
+/// Cast an approval vote on a PENDING_REVIEW workflow.
+///
+/// Shorthand for `transition_state` to APPROVED that also requires the
+/// workflow to be in PENDING_REVIEW; it runs the same checks and builds the
+/// record the same way. The workflow moves to APPROVED once its
+/// `required_approvals` distinct approvers have voted; until then only the
+/// vote is recorded.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to approve
+/// * `comment_hash` - Hash of any comments/justification
+/// * Any optional argument of `transition_state` (`actor_role`,
+///   `category_id`, `request_id`, `external_ref`, `confidential`,
+///   `comment`, `reason_code`, `hash_algo`, `condition_hash`)
+///
+/// # Returns
+///
+/// The workflow's state after the call: APPROVED when the vote completed
+/// the quorum, otherwise PENDING_REVIEW
+///
+/// # Events
+///
+/// `transition` to APPROVED when the vote completes the quorum, followed by
+/// `workflow_completed` when APPROVED is terminal.
+///
+/// # Errors
+///
+/// * `InvalidTransition` - Workflow is not awaiting approval, or the
+///   template does not allow approval from PENDING_REVIEW
+/// * Any error of `transition_state` for a transition to APPROVED
+#[no_mangle]
+pub extern "C" fn cast_approval() {
+    require_not_paused();
+    consume_call_budget();
+    
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    return_value(apply_transition(workflow_id, states::APPROVED, Some(states::PENDING_REVIEW)));
+}
+
+/// Reopen a rejected workflow back into DRAFT for rework.
+///
+/// The REJECTED -> DRAFT edge is only reachable through this entry point;
+/// the prior history is kept and the reopening is appended to it. For
+/// templates with a terminal grace period, reopening is only possible
+/// until the period ends.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to reopen
+/// * `comment_hash` - Hash of any comments/justification
+///
+/// # Events
+///
+/// `transition` from REJECTED to DRAFT.
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow does not exist
+/// * `WorkflowVoided` - Workflow has been voided
+/// * `InvalidTransition` - Workflow is not REJECTED
+/// * `WorkflowAlreadyCompleted` - The terminal grace period has ended
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+/// * `InsufficientPermissions` - Caller did not create the workflow
+/// * `CommentRequired` - The template requires a comment for DRAFT and
+///   comment_hash is all zeros
+/// * `HistoryLimitExceeded` - Workflow history is full
+/// * `TimestampRegression` - Block time precedes the last update
+/// * `TransitionTooSoon` - The template's minimum interval since the last
+///   update has not passed
+/// * `WorkflowLocked` - A nested call is already transitioning the workflow
+#[no_mangle]
+pub extern "C" fn reopen_workflow() {
+    require_not_paused();
+    consume_call_budget();
+    
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let comment_hash: [u8; 32] = runtime::get_named_arg("comment_hash");
+    
+    let caller = runtime::get_caller();
+    let timestamp = get_block_time();
+    
+    let key = workflow_id.to_string();
+    let mut workflow = load_workflow(&key);
+    require_not_voided(&workflow);
+    lock_workflow(&key);
+    check_min_interval(&workflow, timestamp).unwrap_or_revert();
+    
+    // Approved and cancelled workflows stay closed
+    if workflow.current_state != states::REJECTED {
+        runtime::revert(ApiError::User(WorkflowError::InvalidTransition as u16));
+    }
+    
+    if is_grace_over(&workflow, timestamp) {
+        runtime::revert(ApiError::User(WorkflowError::WorkflowAlreadyCompleted as u16));
+    }
+    
+    if caller != workflow.creator {
+        runtime::revert(ApiError::User(WorkflowError::InsufficientPermissions as u16));
+    }
+    check_comment_required(&workflow.template_hash, states::DRAFT, &comment_hash)
+        .unwrap_or_revert();
+    
+    let transition = TransitionRecord {
+        from_state: states::REJECTED,
+        to_state: states::DRAFT,
+        actor: caller,
+        actor_role: read_role_mask(caller),
+        timestamp,
+        comment_hash,
+        category_id: 0,
+        prev_hash: [0u8; 32],
+        external_ref: [0u8; 32],
+        confidential: false,
+        reason_code: 0,
+        hash_algo: hash_algos::KECCAK256,
+        block_height: 0,
+    };
+    
+    commit_transition(&key, &mut workflow, transition);
+    unlock_workflow(&key);
+}
+
+/// Complete a terminal workflow once its template's grace period has ended.
+///
+/// Anyone may call it. Until then the workflow stays open (is_completed
+/// false) so it can still be reopened; afterwards it is locked for good.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to finalize
+///
+/// # Events
+///
+/// `workflow_completed` with the terminal state.
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow does not exist
+/// * `WorkflowVoided` - Workflow has been voided
+/// * `InvalidTransition` - Workflow is not awaiting finalization
+/// * `GracePeriodActive` - The grace period has not ended yet
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+#[no_mangle]
+pub extern "C" fn finalize() {
+    require_not_paused();
+    consume_call_budget();
+    
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let timestamp = get_block_time();
+    
+    let key = workflow_id.to_string();
+    let mut workflow = load_workflow(&key);
+    require_not_voided(&workflow);
+    check_finalize(&workflow, timestamp).unwrap_or_revert();
+    
+    workflow.is_completed = true;
+    let final_state = workflow.current_state;
+    storage::dictionary_put(get_workflows_dict(), &key, workflow);
+    
+    emit_event(
+        "workflow_completed",
+        &[
+            ("workflow_id", workflow_id.to_string()),
+            ("final_state", final_state.to_string()),
+            ("timestamp", timestamp.to_string()),
+        ],
+    );
+}
+
+/// Force a workflow into a state, bypassing the transition rules.
+///
+/// Break-glass correction for workflows left in a wrong state by an
+/// off-chain bug. The change is recorded like any transition, with
+/// `roles::OVERRIDE_FLAG` set in the record's actor_role so auditors can
+/// tell it apart, and completion follows the target state. Any partial
+/// quorum is discarded. Works while the contract is paused.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to correct
+/// * `to_state` - The state to set (predefined or registered)
+/// * `comment_hash` - Hash of the justification for the override
+///
+/// # Events
+///
+/// `transition` with the previous and new state (plus `workflow_completed`
+/// when the new state is terminal), then `admin_override`.
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+/// * `WorkflowNotFound` - Workflow does not exist
+/// * `InvalidArgument` - Unknown state, or the workflow is already in it
+/// * `HistoryLimitExceeded` - Workflow history is full
+/// * `TimestampRegression` - Block time precedes the last update
+#[no_mangle]
+pub extern "C" fn admin_override_state() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let to_state: u8 = runtime::get_named_arg("to_state");
+    let comment_hash: [u8; 32] = runtime::get_named_arg("comment_hash");
+    require_admin();
+    
+    let caller = runtime::get_caller();
+    let timestamp = get_block_time();
+    
+    let key = workflow_id.to_string();
+    let mut workflow = load_workflow(&key);
+    let from_state = workflow.current_state;
+    
+    if !is_known_state(to_state) || to_state == from_state {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    
+    let transition = TransitionRecord {
+        from_state,
+        to_state,
+        actor: caller,
+        actor_role: read_role_mask(caller) | roles::OVERRIDE_FLAG,
+        timestamp,
+        comment_hash,
+        category_id: 0,
+        prev_hash: [0u8; 32],
+        external_ref: [0u8; 32],
+        confidential: false,
+        reason_code: 0,
+        hash_algo: hash_algos::KECCAK256,
+        block_height: 0,
+    };
+    
+    commit_transition(&key, &mut workflow, transition);
+    
+    let no_approvals: Vec<AccountHash> = Vec::new();
+    storage::dictionary_put(get_pending_approvals_dict(), &key, no_approvals);
+    
+    emit_event(
+        "admin_override",
+        &[
+            ("workflow_id", workflow_id.to_string()),
+            ("from_state", from_state.to_string()),
+            ("to_state", to_state.to_string()),
+            ("actor", caller.to_string()),
+            ("timestamp", timestamp.to_string()),
+        ],
+    );
+}
+
+/// Escalate a PENDING_REVIEW workflow to a named senior approver.
+///
+/// Performs the PENDING_REVIEW -> ESCALATED transition under the same
+/// permission checks as `transition_state` and adds the workflow to the
+/// assignee's worklist (see `get_escalated_for`).
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to escalate
+/// * `assignee` - Senior approver the escalation is assigned to
+/// * `comment_hash` - Hash of any comments/justification
+/// * `condition_hash` - Optional proof of an off-chain condition; must equal
+///   the expected condition when the template rule specifies one
+///
+/// # Events
+///
+/// `transition` from PENDING_REVIEW to ESCALATED.
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow does not exist
+/// * `WorkflowVoided` - Workflow has been voided
+/// * `InvalidTransition` - Workflow is not in PENDING_REVIEW, or the
+///   template does not allow escalation
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+/// * `InsufficientPermissions` - Caller may not escalate this workflow
+/// * `InvalidArgument` - Assignee does not hold SENIOR_APPROVER
+/// * `MaxEscalationReached` - Workflow was escalated the maximum number of times
+/// * `ConditionNotMet` - The template rule expects a condition and
+///   condition_hash is missing or differs
+/// * `CommentRequired` - The template requires a comment for ESCALATED and
+///   comment_hash is all zeros
+/// * `HistoryLimitExceeded` - Workflow history is full
+/// * `TimestampRegression` - Block time precedes the last update
+/// * `TransitionTooSoon` - The template's minimum interval since the last
+///   update has not passed
+/// * `WorkflowLocked` - A nested call is already transitioning the workflow
+#[no_mangle]
+pub extern "C" fn escalate_to() {
+    require_not_paused();
+    consume_call_budget();
+    
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let assignee: AccountHash = runtime::get_named_arg("assignee");
+    let comment_hash: [u8; 32] = runtime::get_named_arg("comment_hash");
+    let condition_hash: Option<[u8; 32]> = runtime::try_get_named_arg("condition_hash");
+    
+    let caller = runtime::get_caller();
+    let timestamp = get_block_time();
+    
+    let key = workflow_id.to_string();
+    let mut workflow = load_workflow(&key);
+    require_not_voided(&workflow);
+    lock_workflow(&key);
+    
+    if workflow.current_state != states::PENDING_REVIEW {
+        runtime::revert(ApiError::User(WorkflowError::InvalidTransition as u16));
+    }
+    let actor_role =
+        check_transition(&workflow, states::ESCALATED, caller, None).unwrap_or_revert();
+    check_transition_inputs(&workflow, states::ESCALATED, &comment_hash, condition_hash)
+        .unwrap_or_revert();
+    
+    if read_role_mask(assignee) & roles::SENIOR_APPROVER == 0 {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    
+    let transition = TransitionRecord {
+        from_state: states::PENDING_REVIEW,
+        to_state: states::ESCALATED,
+        actor: caller,
+        actor_role,
+        timestamp,
+        comment_hash,
+        category_id: 0,
+        prev_hash: [0u8; 32],
+        external_ref: [0u8; 32],
+        confidential: false,
+        reason_code: 0,
+        hash_algo: hash_algos::KECCAK256,
+        block_height: 0,
+    };
+    
+    workflow.assigned_approver = Some(assignee);
+    commit_transition(&key, &mut workflow, transition);
+    
+    let mut assigned = read_escalated_for(assignee);
+    assigned.push(workflow_id);
+    storage::dictionary_put(get_escalation_index_dict(), &assignee.to_string(), assigned);
+    unlock_workflow(&key);
+}
+
+/// Escalate a PENDING_REVIEW workflow whose deadline has passed.
+///
+/// A system-driven safety action: anyone may trigger it, and no approver
+/// role is needed, but it only takes effect once the workflow is overdue.
+/// The record names the caller as actor, with `roles::SYSTEM_FLAG` set in
+/// its actor_role so the audit trail shows the escalation was triggered by
+/// the SLA. No senior approver is assigned.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to escalate
+/// * `comment_hash` - Optional hash of any comments (default all zeros)
+/// * `condition_hash` - Optional proof of an off-chain condition; must equal
+///   the expected condition when the template rule specifies one
+///
+/// # Events
+///
+/// `transition` from PENDING_REVIEW to ESCALATED, then `auto_escalate`.
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow does not exist
+/// * `WorkflowVoided` - Workflow has been voided
+/// * `InvalidTransition` - Workflow is not in PENDING_REVIEW, or the
+///   template does not allow escalation
+/// * `NotOverdue` - Workflow has no deadline or it has not passed yet
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+/// * `MaxEscalationReached` - Workflow was escalated the maximum number of times
+/// * `ConditionNotMet` - The template rule expects a condition and
+///   condition_hash is missing or differs
+/// * `CommentRequired` - The template requires a comment for ESCALATED and
+///   comment_hash is missing or all zeros
+/// * `HistoryLimitExceeded` - Workflow history is full
+/// * `TimestampRegression` - Block time precedes the last update
+/// * `TransitionTooSoon` - The template's minimum interval since the last
+///   update has not passed
+/// * `WorkflowLocked` - A nested call is already transitioning the workflow
+#[no_mangle]
+pub extern "C" fn auto_escalate() {
+    require_not_paused();
+    consume_call_budget();
+    
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let comment_hash: [u8; 32] = runtime::try_get_named_arg("comment_hash").unwrap_or([0u8; 32]);
+    let condition_hash: Option<[u8; 32]> = runtime::try_get_named_arg("condition_hash");
+    
+    let caller = runtime::get_caller();
+    let timestamp = get_block_time();
+    
+    let key = workflow_id.to_string();
+    let mut workflow = load_workflow(&key);
+    require_not_voided(&workflow);
+    lock_workflow(&key);
+    
+    if workflow.current_state != states::PENDING_REVIEW {
+        runtime::revert(ApiError::User(WorkflowError::InvalidTransition as u16));
+    }
+    let actor_role =
+        check_overdue_transition(&workflow, states::ESCALATED, caller).unwrap_or_revert();
+    check_transition_inputs(&workflow, states::ESCALATED, &comment_hash, condition_hash)
+        .unwrap_or_revert();
+    
+    let deadline = workflow.deadline;
+    let transition = TransitionRecord {
+        from_state: states::PENDING_REVIEW,
+        to_state: states::ESCALATED,
+        actor: caller,
+        actor_role,
+        timestamp,
+        comment_hash,
+        category_id: 0,
+        prev_hash: [0u8; 32],
+        external_ref: [0u8; 32],
+        confidential: false,
+        reason_code: 0,
+        hash_algo: hash_algos::KECCAK256,
+        block_height: 0,
+    };
+    
+    commit_transition(&key, &mut workflow, transition);
+    unlock_workflow(&key);
+    
+    emit_event(
+        "auto_escalate",
+        &[
+            ("workflow_id", workflow_id.to_string()),
+            ("deadline", deadline.to_string()),
+            ("actor", caller.to_string()),
+            ("timestamp", timestamp.to_string()),
+        ],
+    );
+}
+
+/// Withdraw a submitted workflow from PENDING_REVIEW back to DRAFT.
+///
+/// Lets the creator pull back a premature submission for edits before any
+/// reviewer has decided it. Approval votes already cast are discarded.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to withdraw
+/// * `comment_hash` - Hash of any comments/justification
+///
+/// # Events
+///
+/// `transition` from PENDING_REVIEW to DRAFT.
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow does not exist
+/// * `WorkflowVoided` - Workflow has been voided
+/// * `InvalidTransition` - Workflow is not in PENDING_REVIEW
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+/// * `InsufficientPermissions` - Caller did not create the workflow
+/// * `CommentRequired` - The template requires a comment for DRAFT and
+///   comment_hash is all zeros
+/// * `HistoryLimitExceeded` - Workflow history is full
+/// * `TimestampRegression` - Block time precedes the last update
+/// * `TransitionTooSoon` - The template's minimum interval since the last
+///   update has not passed
+/// * `WorkflowLocked` - A nested call is already transitioning the workflow
+#[no_mangle]
+pub extern "C" fn withdraw_to_draft() {
+    require_not_paused();
+    consume_call_budget();
+    
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let comment_hash: [u8; 32] = runtime::get_named_arg("comment_hash");
+    
+    let caller = runtime::get_caller();
+    let timestamp = get_block_time();
+    
+    let key = workflow_id.to_string();
+    let mut workflow = load_workflow(&key);
+    require_not_voided(&workflow);
+    lock_workflow(&key);
+    check_min_interval(&workflow, timestamp).unwrap_or_revert();
+    
+    // Once a reviewer has acted the workflow has left PENDING_REVIEW
+    if workflow.current_state != states::PENDING_REVIEW {
+        runtime::revert(ApiError::User(WorkflowError::InvalidTransition as u16));
+    }
+    
+    if caller != workflow.creator {
+        runtime::revert(ApiError::User(WorkflowError::InsufficientPermissions as u16));
+    }
+    check_comment_required(&workflow.template_hash, states::DRAFT, &comment_hash)
+        .unwrap_or_revert();
+    
+    let transition = TransitionRecord {
+        from_state: states::PENDING_REVIEW,
+        to_state: states::DRAFT,
+        actor: caller,
+        actor_role: read_role_mask(caller),
+        timestamp,
+        comment_hash,
+        category_id: 0,
+        prev_hash: [0u8; 32],
+        external_ref: [0u8; 32],
+        confidential: false,
+        reason_code: 0,
+        hash_algo: hash_algos::KECCAK256,
+        block_height: 0,
+    };
+    
+    commit_transition(&key, &mut workflow, transition);
+    unlock_workflow(&key);
+}
+
+/// Replace the business data hash of a DRAFT workflow.
+///
+/// The change is recorded as a DataChangeRecord in "data_changes" and the
+/// audit stream rather than in the transition history, so the audit trail
+/// shows that and how the data mutated without polluting state analytics.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to update
+/// * `data_hash` - 32-byte hash of the revised business data, produced by
+///   the workflow's `hash_algo`
+///
+/// # Events
+///
+/// `data_update` with the new data hash.
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow does not exist
+/// * `WorkflowVoided` - Workflow has been voided
+/// * `InvalidTransition` - Workflow is not in DRAFT
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+/// * `InsufficientPermissions` - Caller did not create the workflow
+/// * `HistoryLimitExceeded` - Workflow data change log is full
+/// * `TimestampRegression` - Block time precedes the last update
+#[no_mangle]
+pub extern "C" fn update_data_hash() {
+    require_not_paused();
+    consume_call_budget();
+    
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let data_hash: [u8; 32] = runtime::get_named_arg("data_hash");
+    
+    let caller = runtime::get_caller();
+    let timestamp = get_block_time();
+    
+    let workflows_dict = get_workflows_dict();
+    let key = workflow_id.to_string();
+    let mut workflow = load_workflow(&key);
+    require_not_voided(&workflow);
+    
+    // Data is frozen once submitted
+    if workflow.current_state != states::DRAFT {
+        runtime::revert(ApiError::User(WorkflowError::InvalidTransition as u16));
+    }
+    
+    if caller != workflow.creator {
+        runtime::revert(ApiError::User(WorkflowError::InsufficientPermissions as u16));
+    }
+    
+    require_monotonic(&workflow, timestamp);
+    
+    // The revised hash must come from the workflow's recorded algorithm
+    let change = DataChangeRecord {
+        previous_hash: workflow.data_hash,
+        data_hash,
+        hash_algo: workflow.hash_algo,
+        actor: caller,
+        timestamp,
+    };
+    
+    // Data changes share the history length limit
+    let mut changes = read_data_changes(&key);
+    let max_len: u32 = storage::read(get_max_history_len_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(DEFAULT_MAX_HISTORY_LEN);
+    if changes.len() >= max_len as usize {
+        runtime::revert(ApiError::User(WorkflowError::HistoryLimitExceeded as u16));
+    }
+    changes.push(change);
+    storage::dictionary_put(get_data_changes_dict(), &key, changes);
+    record_audit_entry(audit_kinds::DATA_CHANGE, workflow_id);
+    
+    workflow.data_hash = data_hash;
+    workflow.updated_at = timestamp;
+    storage::dictionary_put(workflows_dict, &key, workflow);
+    
+    emit_event(
+        "data_update",
+        &[
+            ("workflow_id", workflow_id.to_string()),
+            ("data_hash", hash_key(&data_hash)),
+            ("actor", caller.to_string()),
+            ("timestamp", timestamp.to_string()),
+        ],
+    );
+}
+
+/// Attach or replace a named off-chain document hash on a workflow, e.g.
+/// "contract", "invoice" or "approval_memo".
+///
+/// Each attachment is updated independently of the data hash and of the
+/// others. Every update is recorded in "metadata_changes" and the audit
+/// stream, carrying the blake2b hash of the attribute name.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to annotate
+/// * `attr_name` - Attachment name; together with the decimal workflow ID
+///   and a separator it must fit in 64 bytes
+/// * `value` - 32-byte hash of the attached document
+///
+/// # Events
+///
+/// `metadata` with the attribute name and new value.
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow does not exist
+/// * `WorkflowVoided` - Workflow has been voided
+/// * `WorkflowAlreadyCompleted` - Workflow is in a terminal state
+/// * `InvalidArgument` - Attribute name is empty or too long
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+/// * `InsufficientPermissions` - Caller did not create the workflow
+#[no_mangle]
+pub extern "C" fn set_metadata() {
+    require_not_paused();
+    consume_call_budget();
+    
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let attr_name: String = runtime::get_named_arg("attr_name");
+    let value: [u8; 32] = runtime::get_named_arg("value");
+    
+    let caller = runtime::get_caller();
+    let timestamp = get_block_time();
+    
+    let workflow = load_workflow(&workflow_id.to_string());
+    require_not_voided(&workflow);
+    
+    if is_template_terminal_state(&workflow.template_hash, workflow.current_state) {
+        runtime::revert(ApiError::User(WorkflowError::WorkflowAlreadyCompleted as u16));
+    }
+    
+    if caller != workflow.creator {
+        runtime::revert(ApiError::User(WorkflowError::InsufficientPermissions as u16));
+    }
+    
+    let key = metadata_key(workflow_id, &attr_name);
+    storage::dictionary_put(get_metadata_dict(), &key, value);
+    
+    let attr_hash = blake2b(attr_name.as_bytes());
+    record_logged_change(
+        audit_kinds::METADATA_CHANGE,
+        get_metadata_changes_dict(),
+        (workflow_id, (attr_hash, value), (caller, timestamp)),
+    );
+    
+    emit_event(
+        "metadata",
+        &[
+            ("workflow_id", workflow_id.to_string()),
+            ("attr", attr_name),
+            ("value", hash_key(&value)),
+            ("actor", caller.to_string()),
+            ("timestamp", timestamp.to_string()),
+        ],
+    );
+}
+
+/// Get a named attachment of a workflow.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+/// * `attr_name` - Attachment name
+///
+/// # Returns
+///
+/// The attachment's 32-byte hash, or None if it was never set
+#[no_mangle]
+pub extern "C" fn get_metadata() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let attr_name: String = runtime::get_named_arg("attr_name");
+    
+    let value: Option<[u8; 32]> =
+        storage::dictionary_get(get_metadata_dict(), &metadata_key(workflow_id, &attr_name))
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    
+    return_value(value);
+}
+
+/// Void a mistaken workflow without deleting it.
+///
+/// The workflow and its history stay readable, with `is_voided` set so
+/// clients can filter it, but it no longer accepts transitions, votes or
+/// data updates and drops out of deadline tracking.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to void
+///
+/// # Events
+///
+/// `void` with the voiding account.
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+/// * `WorkflowNotFound` - Workflow does not exist
+/// * `WorkflowVoided` - Workflow is already voided
+#[no_mangle]
+pub extern "C" fn void_workflow() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    require_admin();
+    
+    let caller = runtime::get_caller();
+    let key = workflow_id.to_string();
+    let mut workflow = load_workflow(&key);
+    require_not_voided(&workflow);
+    
+    workflow.is_voided = true;
+    update_deadline_index(workflow_id, workflow.deadline, 0);
+    storage::dictionary_put(get_workflows_dict(), &key, workflow);
+    
+    emit_event(
+        "void",
+        &[
+            ("workflow_id", workflow_id.to_string()),
+            ("actor", caller.to_string()),
+            ("timestamp", get_block_time().to_string()),
+        ],
+    );
+}
+
+/// Move a completed workflow and its history into the archive.
+///
+/// The active entries are replaced by a tombstone and an empty history, and
+/// the workflow is dropped from its state bucket and its creator's list, so
+/// it no longer appears in active queries. Workflow and state counts are
+/// unchanged, so `get_count_for_state` keeps counting archived workflows
+/// that `get_workflows_in_state` no longer lists.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to archive
+///
+/// # Events
+///
+/// `archive` with the archiving account.
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow does not exist or is already archived
+/// * `InvalidTransition` - Workflow is not completed
+/// * `InsufficientPermissions` - Caller is neither the creator nor the admin
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+#[no_mangle]
+pub extern "C" fn archive_workflow() {
+    require_not_paused();
+    consume_call_budget();
+    
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    let caller = runtime::get_caller();
+    let key = workflow_id.to_string();
+    let workflow = load_workflow(&key);
+    
+    if caller != workflow.creator && caller != read_admin() {
+        runtime::revert(ApiError::User(WorkflowError::InsufficientPermissions as u16));
+    }
+    if !workflow.is_completed {
+        runtime::revert(ApiError::User(WorkflowError::InvalidTransition as u16));
+    }
+    
+    let transitions_dict = get_transitions_dict();
+    let transitions: Vec<TransitionRecord> = storage::dictionary_get(transitions_dict, &key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default();
+    
+    // Drop the workflow from the active indexes
+    let state_key = workflow.current_state.to_string();
+    let mut bucket = read_state_index(workflow.current_state);
+    bucket.retain(|id| *id != workflow_id);
+    storage::dictionary_put(get_state_index_dict(), &state_key, bucket);
+    
+    let mut created = read_creator_workflows(workflow.creator);
+    created.retain(|id| *id != workflow_id);
+    storage::dictionary_put(get_creator_index_dict(), &workflow.creator.to_string(), created);
+    
+    storage::dictionary_put(get_workflows_archive_dict(), &key, workflow);
+    storage::dictionary_put(get_transitions_archive_dict(), &key, transitions);
+    
+    // Tombstone the active entries
+    storage::dictionary_put(get_workflows_dict(), &key, ());
+    storage::dictionary_put(transitions_dict, &key, Vec::<TransitionRecord>::new());
+    
+    emit_event(
+        "archive",
+        &[
+            ("workflow_id", workflow_id.to_string()),
+            ("actor", caller.to_string()),
+            ("timestamp", get_block_time().to_string()),
+        ],
+    );
+}
+
+/// Store the text of a transition's comment on-chain.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow the transition belongs to
+/// * `index` - Index of the transition in the workflow's history
+/// * `comment` - Comment text whose hash under the record's hash_algo
+///   (keccak256 or blake2b) equals its comment_hash
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - No transition at this index
+/// * `InsufficientPermissions` - Caller did not perform the transition
+/// * `InvalidArgument` - Comment too long or not matching comment_hash, or
+///   the record is confidential (stored text would be publicly readable)
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+#[no_mangle]
+pub extern "C" fn store_comment() {
+    require_not_paused();
+    consume_call_budget();
+    
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let index: u64 = runtime::get_named_arg("index");
+    let comment: String = runtime::get_named_arg("comment");
+    
+    let transitions: Vec<TransitionRecord> =
+        storage::dictionary_get(get_transitions_dict(), &workflow_id.to_string())
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+            .unwrap_or_default();
+    let transition = transitions
+        .get(index as usize)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::WorkflowNotFound as u16));
+    
+    if transition.actor != runtime::get_caller() {
+        runtime::revert(ApiError::User(WorkflowError::InsufficientPermissions as u16));
+    }
+    if transition.confidential {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    validate_comment(&comment, &transition.comment_hash, transition.hash_algo);
+    
+    storage::dictionary_put(get_comments_dict(), &comment_key(workflow_id, index), comment);
+}
+
+/// Get the stored text of a transition's comment.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow the transition belongs to
+/// * `index` - Index of the transition in the workflow's history
+///
+/// # Returns
+///
+/// The comment text, or None if none was stored. Comments of confidential
+/// records are only returned to AUDITOR or ADMIN callers.
+#[no_mangle]
+pub extern "C" fn get_comment() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let index: u64 = runtime::get_named_arg("index");
+    
+    let transitions: Vec<TransitionRecord> =
+        storage::dictionary_get(get_transitions_dict(), &workflow_id.to_string())
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+            .unwrap_or_default();
+    
+    // Confidential comments are withheld like their hashes
+    let visible = transitions.get(index as usize).is_some_and(|transition| {
+        !transition.confidential
+            || read_role_mask(runtime::get_caller()) & (roles::AUDITOR | roles::ADMIN) != 0
+    });
+    let comment: Option<String> = if visible {
+        storage::dictionary_get(get_comments_dict(), &comment_key(workflow_id, index))
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+    } else {
+        None
+    };
+    
+    return_value(comment);
+}
+
+/// Get the current state of a workflow.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+///
+/// # Returns
+///
+/// The WorkflowData struct. While a terminal grace period runs,
+/// finalizes_at is the block time (ms) at which it ends.
+#[no_mangle]
+pub extern "C" fn get_workflow_state() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    let key = workflow_id.to_string();
+    
+    let workflow = load_workflow(&key);
+    
+    return_value(workflow);
+}
+
+/// Check that a workflow's immutable creation fields still match the
+/// genesis_hash committed when it was created.
+///
+/// A mismatch signals corruption or a bug, as those fields are never
+/// written after creation. A reassigned workflow is checked against its
+/// creator at creation, kept in "original_creators". Workflows created
+/// before WorkflowData v9 carry no commitment and report false.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to verify
+///
+/// # Returns
+///
+/// true if the recomputed hash equals the stored genesis_hash
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow does not exist
+#[no_mangle]
+pub extern "C" fn verify_integrity() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    let workflow = load_workflow(&workflow_id.to_string());
+    
+    return_value(integrity_holds(&workflow, read_original_creator(&workflow)));
+}
+
+/// Get the fields of a workflow needed to render it in a list.
+///
+/// A lighter read than `get_workflow_state` for list views.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+///
+/// # Returns
+///
+/// ((id, current_state), (creator, assigned_approver), (is_completed, updated_at))
+#[no_mangle]
+pub extern "C" fn get_workflow_summary() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    let key = workflow_id.to_string();
+    
+    let workflow = load_workflow(&key);
+    let summary: WorkflowSummary = (
+        (workflow.id, workflow.current_state),
+        (workflow.creator, workflow.assigned_approver),
+        (workflow.is_completed, workflow.updated_at),
+    );
+    
+    return_value(summary);
+}
+
+/// Get the progress of a workflow's approval quorum, e.g. to show
+/// "2 of 3 approvals collected".
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+///
+/// # Returns
+///
+/// Tuple of (approvers who voted so far, approvals required); the set is
+/// empty before the first vote and once the workflow leaves PENDING_REVIEW
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow does not exist
+#[no_mangle]
+pub extern "C" fn get_pending_approvals() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    let key = workflow_id.to_string();
+    let workflow = load_workflow(&key);
+    
+    return_value((read_approvals(&key), workflow.required_approvals));
+}
+
+/// Get an archived workflow.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+///
+/// # Returns
+///
+/// The archived WorkflowData struct
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow has not been archived
+#[no_mangle]
+pub extern "C" fn get_archived_workflow() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    let workflow: WorkflowData =
+        storage::dictionary_get(get_workflows_archive_dict(), &workflow_id.to_string())
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::WorkflowNotFound as u16));
+    
+    return_value(workflow);
+}
+
+/// Get the transition history of an archived workflow.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+///
+/// # Returns
+///
+/// Vector of TransitionRecords (empty if not archived), with confidential
+/// comment hashes zeroed unless the caller holds AUDITOR or ADMIN
+#[no_mangle]
+pub extern "C" fn get_archived_history() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    let mut transitions: Vec<TransitionRecord> =
+        storage::dictionary_get(get_transitions_archive_dict(), &workflow_id.to_string())
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+            .unwrap_or_default();
+    redact_for_caller(&mut transitions);
+    
+    return_value(transitions);
+}
+
+/// Get the transition history of a workflow.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+///
+/// # Returns
+///
+/// Vector of TransitionRecord. Confidential comment hashes are zeroed
+/// unless the caller holds AUDITOR or ADMIN.
+#[no_mangle]
+pub extern "C" fn get_workflow_history() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    let transitions_dict = get_transitions_dict();
+    let key = workflow_id.to_string();
+    
+    let mut transitions: Vec<TransitionRecord> = storage::dictionary_get(transitions_dict, &key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default();
+    redact_for_caller(&mut transitions);
+    
+    return_value(transitions);
+}
+
+/// Get a single record of the transition history of a workflow, e.g. to
+/// find who approved it without returning the whole history.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+/// * `index` - Position of the record (0 is the oldest record not purged)
+///
+/// # Returns
+///
+/// The TransitionRecord at `index`, with a confidential comment hash
+/// zeroed unless the caller holds AUDITOR or ADMIN
+///
+/// # Errors
+///
+/// * `IndexOutOfBounds` - The history has no record at `index`
+#[no_mangle]
+pub extern "C" fn get_transition_at() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let index: u64 = runtime::get_named_arg("index");
+    
+    let transitions: Vec<TransitionRecord> =
+        storage::dictionary_get(get_transitions_dict(), &workflow_id.to_string())
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+            .unwrap_or_default();
+    
+    let mut record = transitions
+        .into_iter()
+        .nth(index as usize)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::IndexOutOfBounds as u16));
+    redact_for_caller(core::slice::from_mut(&mut record));
+    
+    return_value(record);
+}
+
+/// Get the number of records in the transition history of a workflow.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+///
+/// # Returns
+///
+/// History length (u64); zero for unknown workflows
+#[no_mangle]
+pub extern "C" fn get_transition_count() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    let transitions: Vec<TransitionRecord> =
+        storage::dictionary_get(get_transitions_dict(), &workflow_id.to_string())
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+            .unwrap_or_default();
+    
+    return_value(transitions.len() as u64);
+}
+
+/// Get the data hash changes of a workflow.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+///
+/// # Returns
+///
+/// Vector of DataChangeRecord, oldest first; empty if the data was never
+/// changed
+#[no_mangle]
+pub extern "C" fn get_data_changes() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    return_value(read_data_changes(&workflow_id.to_string()));
+}
+
+/// Get one page of the transition history of a workflow.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+/// * `offset` - Index of the first record to return
+/// * `limit` - Maximum number of records to return (capped at 100)
+///
+/// # Returns
+///
+/// Tuple of (records in the page, total number of records). Offsets past
+/// the end return an empty page. Confidential comment hashes are zeroed
+/// unless the caller holds AUDITOR or ADMIN.
+#[no_mangle]
+pub extern "C" fn get_workflow_history_paged() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let offset: u64 = runtime::get_named_arg("offset");
+    let limit: u64 = runtime::get_named_arg("limit");
+    
+    let transitions_dict = get_transitions_dict();
+    let key = workflow_id.to_string();
+    
+    let transitions: Vec<TransitionRecord> = storage::dictionary_get(transitions_dict, &key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default();
+    
+    let total = transitions.len() as u64;
+    let mut page: Vec<TransitionRecord> = transitions
+        .into_iter()
+        .skip(offset.min(total) as usize)
+        .take(limit.min(MAX_PAGE_SIZE) as usize)
+        .collect();
+    redact_for_caller(&mut page);
+    
+    return_value((page, total));
+}
+
+/// Get the transition histories of several workflows in one call, e.g. for
+/// an auditor reviewing a batch.
+///
+/// At most 100 workflow IDs are accepted and at most 500 records are
+/// returned in total. Once the record budget runs out, the history that
+/// exhausted it is cut short, later workflows are omitted, and the result
+/// is flagged as truncated; clients continue with
+/// `get_workflow_history_paged` for the cut history and a new batch for the
+/// rest. IDs without a live history (nonexistent or archived workflows)
+/// are skipped.
+///
+/// # Arguments
+///
+/// * `workflow_ids` - The workflows to query
+///
+/// # Returns
+///
+/// Tuple of ((workflow_id, records) per workflow in input order, truncated
+/// flag). Confidential comment hashes are zeroed unless the caller holds
+/// AUDITOR or ADMIN.
+///
+/// # Errors
+///
+/// * `InvalidArgument` - More than 100 workflow IDs
+#[no_mangle]
+pub extern "C" fn get_histories_batch() {
+    let workflow_ids: Vec<U256> = runtime::get_named_arg("workflow_ids");
+    
+    if workflow_ids.len() as u64 > MAX_PAGE_SIZE {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    
+    let transitions_dict = get_transitions_dict();
+    let (mut histories, truncated) =
+        collect_histories(workflow_ids, MAX_BATCH_RECORDS as usize, |workflow_id| {
+            // Archived workflows leave a tombstone and an empty history behind
+            let key = workflow_id.to_string();
+            read_workflow(&key)?;
+            storage::dictionary_get(transitions_dict, &key)
+                .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        });
+    for (_, transitions) in histories.iter_mut() {
+        redact_for_caller(transitions);
+    }
+    
+    return_value((histories, truncated));
+}
+
+/// Get the transitions of a workflow matching the given filters.
+///
+/// Omitted filters match every record.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+/// * `actor` - Optional account that performed the transition
+/// * `from_state` - Optional state the transition left
+/// * `to_state` - Optional state the transition entered
+///
+/// # Returns
+///
+/// Matching TransitionRecords in order (empty if none match), with
+/// confidential comment hashes zeroed unless the caller holds AUDITOR or ADMIN
+#[no_mangle]
+pub extern "C" fn get_workflow_history_filtered() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let actor: Option<AccountHash> = runtime::try_get_named_arg("actor");
+    let from_state: Option<u8> = runtime::try_get_named_arg("from_state");
+    let to_state: Option<u8> = runtime::try_get_named_arg("to_state");
+    
+    let transitions_dict = get_transitions_dict();
+    let key = workflow_id.to_string();
+    
+    let transitions: Vec<TransitionRecord> = storage::dictionary_get(transitions_dict, &key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default();
+    
+    let mut matching: Vec<TransitionRecord> = transitions
+        .into_iter()
+        .filter(|t| actor.is_none_or(|actor| t.actor == actor))
+        .filter(|t| from_state.is_none_or(|state| t.from_state == state))
+        .filter(|t| to_state.is_none_or(|state| t.to_state == state))
+        .collect();
+    redact_for_caller(&mut matching);
+    
+    return_value(matching);
+}
+
+/// Verify the hash chain linking a workflow's transition records.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to verify
+///
+/// # Returns
+///
+/// True if every record's prev_hash matches the hash of the record before it;
+/// after a purge the oldest retained record links to the last purged one
+#[no_mangle]
+pub extern "C" fn verify_chain() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    let transitions_dict = get_transitions_dict();
+    let key = workflow_id.to_string();
+    
+    let transitions: Vec<TransitionRecord> = storage::dictionary_get(transitions_dict, &key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default();
+    
+    let (_, anchor) = read_history_purge(workflow_id);
+    return_value(chain_intact(&transitions, anchor, transition_hash));
+}
+
+/// Dry-run a transition for the caller without mutating state.
+///
+/// Lets front-ends disable actions the caller cannot take before any gas
+/// is spent on a doomed deploy.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to transition
+/// * `to_state` - The target state
+/// * `actor_role` - Optional claimed role mask
+///
+/// # Returns
+///
+/// 0 if the transition would pass, otherwise the `WorkflowError` code
+/// `transition_state` would revert with (as u32, since CLValue has no u16)
+#[no_mangle]
+pub extern "C" fn validate_transition() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let to_state: u8 = runtime::get_named_arg("to_state");
+    let claimed_role: Option<u64> = runtime::try_get_named_arg("actor_role");
+    
+    let workflow = read_workflow(&workflow_id.to_string());
+    
+    let caller = runtime::get_caller();
+    let outcome = workflow
+        .ok_or(WorkflowError::WorkflowNotFound)
+        .and_then(|workflow| check_transition(&workflow, to_state, caller, claimed_role));
+    let code: u32 = match outcome {
+        Ok(_) => 0,
+        Err(error) => error as u32,
+    };
+    
+    return_value(code);
+}
+
+/// Check whether an account could perform a transition.
+///
+/// Applies the checks of `transition_state` to `account` instead of the
+/// caller, using its registered roles and active delegations, so UIs can
+/// render per-user controls. An account that already voted towards a
+/// pending quorum cannot approve again.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to transition
+/// * `to_state` - The target state
+/// * `account` - The account that would perform the transition
+///
+/// # Returns
+///
+/// True if the transition would pass; false otherwise, including for
+/// nonexistent workflows
+#[no_mangle]
+pub extern "C" fn can_transition() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let to_state: u8 = runtime::get_named_arg("to_state");
+    let account: AccountHash = runtime::get_named_arg("account");
+    
+    let key = workflow_id.to_string();
+    let allowed = read_workflow(&key).is_some_and(|workflow| {
+        let quorum_vote =
+            workflow.current_state == states::PENDING_REVIEW && to_state == states::APPROVED;
+        check_transition(&workflow, to_state, account, None).is_ok()
+            && !(quorum_vote && has_approved(&key, account))
+    });
+    
+    return_value(allowed);
+}
+
+/// Check whether an account is the creator of a workflow, without returning
+/// the whole WorkflowData, for permission-gating UIs.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+/// * `account` - The account to compare with the creator
+///
+/// # Returns
+///
+/// True if `account` is the workflow's current creator; false otherwise,
+/// including for nonexistent workflows
+#[no_mangle]
+pub extern "C" fn is_creator() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let account: AccountHash = runtime::get_named_arg("account");
+    
+    let is_creator = read_workflow(&workflow_id.to_string())
+        .is_some_and(|workflow| workflow.creator == account);
+    
+    return_value(is_creator);
+}
+
+/// Get the roles permitted to move a workflow from its current state to a
+/// target state, so UIs can say who is needed before anyone attempts it.
+///
+/// Uses the workflow template's transition rules, or the built-in state
+/// machine when the template defines none.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to transition
+/// * `to_state` - The target state
+///
+/// # Returns
+///
+/// Role mask of which any one role suffices; 0 if the transition is not
+/// allowed at all
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow does not exist
+#[no_mangle]
+pub extern "C" fn required_role_for() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let to_state: u8 = runtime::get_named_arg("to_state");
+    
+    let workflow = load_workflow(&workflow_id.to_string());
+    let required =
+        template_required_role(&workflow.template_hash, workflow.current_state, to_state);
+    
+    return_value(required.unwrap_or(0));
+}
+
+/// Explain why a transition by the caller would fail.
+///
+/// A reverted deploy rolls back every write, so the contract cannot leave
+/// a `last_error` record behind for a failed `transition_state`. Clients
+/// instead call this read-only entry point, before or after a failure, to
+/// obtain the same diagnostic.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to transition
+/// * `to_state` - The target state
+/// * `actor_role` - Optional claimed role mask
+///
+/// # Returns
+///
+/// Tuple of (error code or 0 if the transition would pass these checks,
+/// workflow ID, workflow's current state or 0 if it does not exist).
+/// Category, comment and history-limit checks are not covered.
+#[no_mangle]
+pub extern "C" fn diagnose_transition() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let to_state: u8 = runtime::get_named_arg("to_state");
+    let claimed_role: Option<u64> = runtime::try_get_named_arg("actor_role");
+    let caller = runtime::get_caller();
+    
+    let workflow = read_workflow(&workflow_id.to_string());
+    
+    let diagnostic: (u32, U256, u8) = match workflow {
+        None => (WorkflowError::WorkflowNotFound as u32, workflow_id, 0),
+        Some(workflow) => {
+            let code = match check_transition(&workflow, to_state, caller, claimed_role) {
+                Ok(_) => 0,
+                Err(error) => error as u32,
+            };
+            (code, workflow_id, workflow.current_state)
+        }
+    };
+    
+    return_value(diagnostic);
+}
+
+/// Check whether moving a workflow to a state would complete it.
+///
+/// Lets clients confirm irreversible terminal actions before submitting.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+/// * `to_state` - The prospective target state
+///
+/// # Returns
+///
+/// True if `to_state` is terminal for the workflow
+#[no_mangle]
+pub extern "C" fn is_terminal_transition() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let to_state: u8 = runtime::get_named_arg("to_state");
+    
+    let key = workflow_id.to_string();
+    
+    let workflow = load_workflow(&key);
+    let terminal = is_template_terminal_state(&workflow.template_hash, to_state);
+    
+    return_value(terminal);
+}
+
+/// Get the ordered path of states a workflow has traversed.
+///
+/// Starts from the workflow's initial state and follows each transition's
+/// `to_state`, collapsing consecutive repeats.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+///
+/// # Returns
+///
+/// Vector of visited states (u8)
+#[no_mangle]
+pub extern "C" fn get_state_path() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    let key = workflow_id.to_string();
+    
+    let workflow = load_workflow(&key);
+    
+    let transitions_dict = get_transitions_dict();
+    let transitions: Vec<TransitionRecord> = storage::dictionary_get(transitions_dict, &key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_default();
+    
+    return_value(state_path(&transitions, workflow.current_state));
+}
+
+/// Follow a history from its first from_state through each to_state,
+/// collapsing consecutive repeats. An empty history yields `current_state`.
+fn state_path(transitions: &[TransitionRecord], current_state: u8) -> Vec<u8> {
+    let initial_state = transitions
+        .first()
+        .map(|t| t.from_state)
+        .unwrap_or(current_state);
+    
+    let mut path: Vec<u8> = vec![initial_state];
+    for transition in transitions.iter() {
+        if path.last() != Some(&transition.to_state) {
+            path.push(transition.to_state);
+        }
+    }
+    path
+}
+
+/// Get the average time workflows spent in a state before leaving it.
+///
+/// # Arguments
+///
+/// * `state` - The state to query
+///
+/// # Returns
+///
+/// Mean dwell time in blocktime units (milliseconds), or 0 if no workflow
+/// has left the state yet
+#[no_mangle]
+pub extern "C" fn get_avg_time_in_state() {
+    let state: u8 = runtime::get_named_arg("state");
+    
+    return_value(average_time(read_time_in_state(state)));
+}
+
+/// Check whether a workflow has missed the deadline for its current state.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+///
+/// # Returns
+///
+/// True if the workflow is not completed and its deadline has passed
+#[no_mangle]
+pub extern "C" fn check_overdue() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    let key = workflow_id.to_string();
+    
+    let workflow = load_workflow(&key);
+    
+    let overdue = is_overdue(&workflow, get_block_time());
+    
+    return_value(overdue);
+}
+
+/// Get open workflows whose deadline has passed, most overdue first.
+///
+/// # Arguments
+///
+/// * `now` - Reference blocktime to compare deadlines against
+/// * `limit` - Maximum number of IDs to return (capped at 100)
+///
+/// # Returns
+///
+/// Vector of workflow IDs with a deadline before `now`
+#[no_mangle]
+pub extern "C" fn get_workflows_past_deadline() {
+    let now: u64 = runtime::get_named_arg("now");
+    let limit: u64 = runtime::get_named_arg("limit");
+    
+    let breached = collect_past_deadline(
+        &read_deadline_windows(),
+        read_deadline_bucket,
+        now,
+        limit.min(MAX_PAGE_SIZE) as usize,
+    );
+    
+    return_value(breached);
+}
+
+/// Find the overdue workflows within a contiguous ID range.
+///
+/// Applies the `check_overdue` test to each ID in the range, so a
+/// monitoring job can page through IDs 1..=workflow_count in steps of
+/// `count` for everything needing escalation. Derived IDs lie above the
+/// count and are not covered; `get_workflows_past_deadline` includes them.
+///
+/// # Arguments
+///
+/// * `start_id` - First workflow ID in the range
+/// * `count` - Number of IDs to scan (capped at 100)
+///
+/// # Returns
+///
+/// IDs in the range that are not completed and past their deadline
+#[no_mangle]
+pub extern "C" fn get_overdue_workflows() {
+    let start_id: U256 = runtime::get_named_arg("start_id");
+    let count: u64 = runtime::get_named_arg("count");
+    
+    let last_id = read_workflow_count();
+    let now = get_block_time();
+    
+    let mut overdue: Vec<U256> = Vec::new();
+    let mut workflow_id = start_id;
+    for _ in 0..count.min(MAX_PAGE_SIZE) {
+        if workflow_id > last_id {
+            break;
+        }
+        let workflow = read_workflow(&workflow_id.to_string());
+        if workflow.is_some_and(|workflow| is_overdue(&workflow, now)) {
+            overdue.push(workflow_id);
+        }
+        workflow_id += U256::one();
+    }
+    
+    return_value(overdue);
+}
+
+/// List the ESCALATED workflows assigned to a senior approver.
+///
+/// # Arguments
+///
+/// * `assignee` - The senior approver
+///
+/// # Returns
+///
+/// Workflow IDs in escalation order; empty if none are assigned
+#[no_mangle]
+pub extern "C" fn get_escalated_for() {
+    let assignee: AccountHash = runtime::get_named_arg("assignee");
+    return_value(read_escalated_for(assignee));
+}
+
+/// Get the number of workflows currently in a state.
+///
+/// # Arguments
+///
+/// * `state` - The state to count
+///
+/// # Returns
+///
+/// U256 tally (zero for states never entered). Archived workflows stay
+/// counted in their final state although they are no longer listed there.
+#[no_mangle]
+pub extern "C" fn get_count_for_state() {
+    let state: u8 = runtime::get_named_arg("state");
+    return_value(read_state_count(state));
+}
+
+/// Get how many transitions into a state have been committed across all
+/// workflows, e.g. approvals or rejections for compliance reporting.
+///
+/// Unlike `get_count_for_state`, the count never decreases as workflows
+/// move on.
+///
+/// # Arguments
+///
+/// * `to_state` - The target state to count
+///
+/// # Returns
+///
+/// U256 count (zero for states never entered by a transition)
+#[no_mangle]
+pub extern "C" fn get_transition_type_count() {
+    let to_state: u8 = runtime::get_named_arg("to_state");
+    return_value(read_transition_type_count(to_state));
+}
+
+/// Get the IDs of the workflows currently in a state, for worklist views.
+///
+/// Archived workflows are dropped from their bucket while the state tally
+/// keeps counting them; voided workflows stay listed. Keeping buckets
+/// current costs a scan of the source bucket on every transition, so very
+/// large buckets make leaving them more expensive.
+///
+/// # Arguments
+///
+/// * `state` - The state to list
+/// * `offset` - Optional number of IDs to skip (defaults to 0)
+/// * `limit` - Optional page size (defaults to and capped at 100)
+///
+/// # Returns
+///
+/// Workflow IDs in the order they entered the state
+#[no_mangle]
+pub extern "C" fn get_workflows_in_state() {
+    let state: u8 = runtime::get_named_arg("state");
+    let offset: u64 = runtime::try_get_named_arg("offset").unwrap_or(0);
+    let limit: u64 = runtime::try_get_named_arg("limit").unwrap_or(MAX_PAGE_SIZE);
+    
+    let page: Vec<U256> = read_state_index(state)
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit.min(MAX_PAGE_SIZE) as usize)
+        .collect();
+    
+    return_value(page);
+}
+
+/// Export a contiguous range of workflows for off-chain reconciliation.
+///
+/// Clients page through IDs 1..=workflow_count in steps of `count`.
+/// Derived IDs lie above the count and are not covered.
+///
+/// # Arguments
+///
+/// * `start_id` - First workflow ID in the range
+/// * `count` - Number of IDs to scan (capped at 100)
+///
+/// # Returns
+///
+/// Tuple of (workflows found in the range, number found). IDs that do not
+/// exist are skipped.
+#[no_mangle]
+pub extern "C" fn get_workflows_range() {
+    let start_id: U256 = runtime::get_named_arg("start_id");
+    let count: u64 = runtime::get_named_arg("count");
+    
+    let last_id = read_workflow_count();
+    
+    let mut found: Vec<WorkflowData> = Vec::new();
+    let mut workflow_id = start_id;
+    for _ in 0..count.min(MAX_PAGE_SIZE) {
+        if workflow_id > last_id {
+            break;
+        }
+        if let Some(workflow) = read_workflow(&workflow_id.to_string()) {
+            found.push(workflow);
+        }
+        workflow_id += U256::one();
+    }
+    
+    let found_count = found.len() as u64;
+    return_value((found, found_count));
+}
+
+/// List the workflows created by an account.
+///
+/// # Arguments
+///
+/// * `creator` - The creating account
+/// * `offset` - Optional index of the first ID to return (default 0)
+/// * `limit` - Optional maximum number of IDs to return (capped at 100)
+///
+/// # Returns
+///
+/// Workflow IDs in creation order; empty if the account created none
+#[no_mangle]
+pub extern "C" fn get_workflows_by_creator() {
+    let creator: AccountHash = runtime::get_named_arg("creator");
+    let offset: u64 = runtime::try_get_named_arg("offset").unwrap_or(0);
+    let limit: u64 = runtime::try_get_named_arg("limit").unwrap_or(MAX_PAGE_SIZE);
+    
+    let page: Vec<U256> = read_creator_workflows(creator)
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit.min(MAX_PAGE_SIZE) as usize)
+        .collect();
+    
+    return_value(page);
+}
+
+/// Read the unified audit stream of transitions and role changes.
+///
+/// # Arguments
+///
+/// * `after_seq` - Return entries with a sequence number above this
+///   (0 to start from the beginning)
+/// * `limit` - Maximum number of entries to return (capped at 100)
+///
+/// # Returns
+///
+/// (kind, reference) pairs in sequence order; see `audit_kinds` for how
+/// each reference resolves
+#[no_mangle]
+pub extern "C" fn get_audit_stream() {
+    let after_seq: u64 = runtime::get_named_arg("after_seq");
+    let limit: u64 = runtime::get_named_arg("limit");
+    
+    let last: u64 = storage::read(get_audit_seq_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(0);
+    let stream_dict = get_audit_stream_dict();
+    
+    let entries = collect_audit_entries(after_seq, last, limit.min(MAX_PAGE_SIZE), |seq| {
+        storage::dictionary_get(stream_dict, &seq.to_string())
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+    });
+    
+    return_value(entries);
+}
+
+/// Get a role assignment recorded in the audit stream.
+///
+/// # Arguments
+///
+/// * `seq` - Sequence number of a ROLE_CHANGE audit entry
+///
+/// # Returns
+///
+/// (account, role_mask, (assigning admin, timestamp))
+///
+/// # Errors
+///
+/// * `InvalidArgument` - No role change recorded at this sequence number
+#[no_mangle]
+pub extern "C" fn get_role_change() {
+    let seq: u64 = runtime::get_named_arg("seq");
+    
+    let change: (AccountHash, u64, (AccountHash, u64)) =
+        storage::dictionary_get(get_role_changes_dict(), &seq.to_string())
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::InvalidArgument as u16));
+    
+    return_value(change);
+}
+
+/// Get the total number of workflows created.
+///
+/// # Returns
+///
+/// U256 count
+#[no_mangle]
+pub extern "C" fn get_workflow_count() {
+    let count = read_workflow_count();
+    return_value(count);
+}
+
+/// Get the number of state transitions committed contract-wide.
+///
+/// Counts every committed transition, whichever entry point made it,
+/// including the seed record of an imported workflow; quorum votes that do
+/// not complete the quorum and reverted calls are not counted.
+///
+/// # Returns
+///
+/// U256 count
+#[no_mangle]
+pub extern "C" fn get_total_transitions() {
+    let total = read_total_transitions();
+    return_value(total);
+}
+
+/// Get the number of workflows created from a template.
+///
+/// Each workflow's position in this count is stored as its template_seq.
+///
+/// # Arguments
+///
+/// * `template_hash` - 32-byte hash of the workflow template definition
+///
+/// # Returns
+///
+/// u64 count
+#[no_mangle]
+pub extern "C" fn get_template_count() {
+    let template_hash: [u8; 32] = runtime::get_named_arg("template_hash");
+    return_value(read_template_count(&template_hash));
+}
+
+/// Get the block timestamp at which the contract was installed.
+///
+/// # Returns
+///
+/// u64 blocktime
+#[no_mangle]
+pub extern "C" fn get_deployed_at() {
+    let uref = runtime::get_key(DEPLOYED_AT_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let deployed_at: u64 = storage::read(uref)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(0);
+    return_value(deployed_at);
+}
+
+/// Get the Merkle root over all workflows' audit digests as of the last
+/// `update_global_root` sweep.
+///
+/// Leaves are ordered by workflow ID and cover IDs 1..="global_tree_size";
+/// an archived or missing workflow contributes its last swept digest, or a
+/// zero leaf if it never had one.
+///
+/// # Returns
+///
+/// 32-byte root (all zeros before the first sweep)
+#[no_mangle]
+pub extern "C" fn get_global_root() {
+    let root: [u8; 32] = storage::read(get_global_root_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or([0u8; 32]);
+    return_value(root);
+}
+
+/// Get the installed contract version.
+///
+/// # Returns
+///
+/// Tuple of (semver string, WorkflowData schema version)
+#[no_mangle]
+pub extern "C" fn get_contract_version() {
+    let uref = runtime::get_key(CONTRACT_VERSION_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let version: String = storage::read(uref)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    return_value((version, WORKFLOW_DATA_VERSION));
+}
+
+/// Get the hash of the contract package, for tooling that upgrades the
+/// contract or sends package-addressed transactions.
+///
+/// The package access URef is deliberately not exposed: it stays with the
+/// installer under "workflow_contract_access", as holding it grants control
+/// over the package.
+///
+/// # Returns
+///
+/// The 32-byte package hash
+#[no_mangle]
+pub extern "C" fn get_package_hash() {
+    let package_hash = runtime::get_key(PACKAGE_HASH_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_hash_addr()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    return_value(package_hash);
+}
+
+/// Get the byte layout of every stored record type, so off-chain decoders
+/// can check which schema version they are reading against.
+///
+/// # Returns
+///
+/// List of (record name, schema version, fields), where fields are the
+/// `name:type` pairs in serialization order after the leading version byte
+#[no_mangle]
+pub extern "C" fn get_schema() {
+    let describe = |name: &str, version: u8, layout: &[&str]| {
+        (
+            name.to_string(),
+            version,
+            layout.iter().map(|field| field.to_string()).collect::<Vec<String>>(),
+        )
+    };
+    
+    return_value(vec![
+        describe("WorkflowData", WORKFLOW_DATA_VERSION, WORKFLOW_DATA_LAYOUT),
+        describe("TransitionRecord", TRANSITION_RECORD_VERSION, TRANSITION_RECORD_LAYOUT),
+        describe("DataChangeRecord", DATA_CHANGE_RECORD_VERSION, DATA_CHANGE_RECORD_LAYOUT),
+    ]);
+}
+
+/// Get a health summary of the contract in a single call, for monitoring.
+///
+/// # Returns
+///
+/// Tuple of ((semver string, WorkflowData schema version),
+/// (workflow count, total transitions), paused flag); counters are zero on
+/// a freshly installed contract
+#[no_mangle]
+pub extern "C" fn get_contract_info() {
+    let uref = runtime::get_key(CONTRACT_VERSION_KEY)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let version: String = storage::read(uref)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_else(|| CONTRACT_VERSION.to_string());
+    
+    return_value((
+        (version, WORKFLOW_DATA_VERSION),
+        (read_workflow_count(), read_total_transitions()),
+        read_paused(),
+    ));
+}
+
+/// Register a compliance proof for an approved workflow.
+/// 
+/// This entry point stores a cryptographic hash of the compliance proof JSON
+/// on-chain, providing immutable evidence that the workflow was approved
+/// with specific documents reviewed.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow ID (U256) to register proof for
+/// * `proof_hash` - SHA-256 hash of the compliance proof JSON (32 bytes)
+///
+/// # Errors
+///
+/// * `WorkflowNotFound` - Workflow does not exist
+/// * `WorkflowVoided` - Workflow has been voided
+/// * `WorkflowNotApproved` - Workflow is not in APPROVED state
+/// * `ComplianceProofAlreadyExists` - Proof already registered for this workflow
+/// * `ContractPaused` - Contract is paused
+/// * `RateLimited` - Caller exhausted its call budget for the window
+#[no_mangle]
+pub extern "C" fn register_compliance_proof() {
+    require_not_paused();
+    consume_call_budget();
+    
+    // Get arguments
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let proof_hash: [u8; 32] = runtime::get_named_arg("proof_hash");
+    
+    // Load workflow to verify it exists and is approved
+    let key = workflow_id.to_string();
+    
+    let workflow = load_workflow(&key);
+    require_not_voided(&workflow);
+    
+    // Verify workflow is in APPROVED state
+    if workflow.current_state != states::APPROVED {
+        runtime::revert(ApiError::User(WorkflowError::WorkflowNotApproved as u16));
+    }
+    
+    // Check if proof already exists for this workflow
+    let proofs_dict = get_compliance_proofs_dict();
+    let existing: Option<[u8; 32]> = storage::dictionary_get(proofs_dict, &key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    
+    if existing.is_some() {
+        runtime::revert(ApiError::User(WorkflowError::ComplianceProofAlreadyExists as u16));
+    }
+    
+    // Store the compliance proof hash (immutable - can only be set once)
+    storage::dictionary_put(proofs_dict, &key, proof_hash);
+}
+
+/// Get the compliance proof hash for a workflow.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to query
+///
+/// # Returns
+///
+/// The 32-byte proof hash, or reverts if not found
+#[no_mangle]
+pub extern "C" fn get_compliance_proof() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    
+    let proofs_dict = get_compliance_proofs_dict();
+    let key = workflow_id.to_string();
+    
+    let proof_hash: [u8; 32] = storage::dictionary_get(proofs_dict, &key)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::WorkflowNotFound as u16));
+    
+    return_value(proof_hash);
+}
+
+/// Define the allowed transitions for a workflow template.
+///
+/// Workflows created from the template are validated against these rules
+/// instead of the built-in state machine. Calling again replaces the rules.
+///
+/// # Arguments
+///
+/// * `template_hash` - The template the rules apply to
+/// * `rules` - List of (from_state, to_state, required_role_mask)
+/// * `allow_self_approval` - Optional flag letting creators approve their
+///   own workflows (default false, enforcing segregation of duties)
+/// * `comment_required_states` - Optional target states (typically
+///   REJECTED) whose transitions must carry a non-zero comment_hash
+///   (default none)
+/// * `rule_conditions` - Optional list of (from_state, to_state,
+///   expected_condition) gating rules on a matching condition_hash supplied
+///   to `transition_state` (default none)
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+/// * `InvalidWorkflowDefinition` - Rules are empty, repeat a transition, or
+///   leave out the mandatory role of an escalation transition, or a
+///   condition repeats a transition or names one without a rule
+#[no_mangle]
+pub extern "C" fn define_transition_rules() {
+    let template_hash: [u8; 32] = runtime::get_named_arg("template_hash");
+    let rules: Vec<TransitionRule> = runtime::get_named_arg("rules");
+    let allow_self_approval: bool =
+        runtime::try_get_named_arg("allow_self_approval").unwrap_or(false);
+    let comment_required_states: Vec<u8> =
+        runtime::try_get_named_arg("comment_required_states").unwrap_or_default();
+    let rule_conditions: Vec<RuleCondition> =
+        runtime::try_get_named_arg("rule_conditions").unwrap_or_default();
+    require_admin();
+    
+    // Reject empty or ambiguous rule sets
+    if rules.is_empty() {
+        runtime::revert(ApiError::User(WorkflowError::InvalidWorkflowDefinition as u16));
+    }
+    for (index, (from, to, _)) in rules.iter().enumerate() {
+        if rules[..index].iter().any(|(f, t, _)| f == from && t == to) {
+            runtime::revert(ApiError::User(WorkflowError::InvalidWorkflowDefinition as u16));
+        }
+    }
+    
+    // Escalation transitions must stay reachable by their mandatory role
+    for (from, to, role_mask) in rules.iter() {
+        if mandatory_role(*from, *to).is_some_and(|mandatory| role_mask & mandatory == 0) {
+            runtime::revert(ApiError::User(WorkflowError::InvalidWorkflowDefinition as u16));
+        }
+    }
+    
+    // Conditions must gate exactly one defined rule each
+    for (index, (from, to, _)) in rule_conditions.iter().enumerate() {
+        let is_repeated = rule_conditions[..index].iter().any(|(f, t, _)| f == from && t == to);
+        let has_rule = rules.iter().any(|(f, t, _)| f == from && t == to);
+        if is_repeated || !has_rule {
+            runtime::revert(ApiError::User(WorkflowError::InvalidWorkflowDefinition as u16));
+        }
+    }
+    
+    storage::dictionary_put(get_transition_rules_dict(), &hash_key(&template_hash), rules);
+    storage::dictionary_put(
+        get_self_approval_dict(),
+        &hash_key(&template_hash),
+        allow_self_approval,
+    );
+    storage::dictionary_put(
+        get_comment_required_dict(),
+        &hash_key(&template_hash),
+        comment_required_states,
+    );
+    storage::dictionary_put(
+        get_rule_conditions_dict(),
+        &hash_key(&template_hash),
+        rule_conditions,
+    );
+}
+
+/// Set the SLA for workflows of a template entering a state.
+///
+/// # Arguments
+///
+/// * `template_hash` - The template the SLA applies to
+/// * `state` - The state the SLA applies to
+/// * `sla_seconds` - Time allowed in the state (0 removes the SLA)
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn set_state_sla() {
+    let template_hash: [u8; 32] = runtime::get_named_arg("template_hash");
+    let state: u8 = runtime::get_named_arg("state");
+    let sla_seconds: u64 = runtime::get_named_arg("sla_seconds");
+    require_admin();
+    
+    let key = format!("{}_{}", hash_key(&template_hash), state);
+    storage::dictionary_put(get_state_slas_dict(), &key, sla_seconds);
+}
+
+/// Set which states complete workflows of a template.
+///
+/// Replaces the built-in terminal states (APPROVED, REJECTED, CANCELLED and
+/// terminal catalog states) for the template, e.g. to make REJECTED
+/// reworkable through a template rule back to DRAFT. Calling again
+/// replaces the set; an empty list restores the built-in states.
+///
+/// # Arguments
+///
+/// * `template_hash` - The template the terminal states apply to
+/// * `terminal_states` - States that complete the workflow when entered
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+/// * `InvalidArgument` - A state is unknown or listed twice
+#[no_mangle]
+pub extern "C" fn set_terminal_states() {
+    let template_hash: [u8; 32] = runtime::get_named_arg("template_hash");
+    let terminal_states: Vec<u8> = runtime::get_named_arg("terminal_states");
+    require_admin();
+    
+    for (index, state) in terminal_states.iter().enumerate() {
+        if !is_known_state(*state) || terminal_states[..index].contains(state) {
+            runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+        }
+    }
+    
+    storage::dictionary_put(
+        get_terminal_states_dict(),
+        &hash_key(&template_hash),
+        terminal_states,
+    );
+}
+
+/// Set the pool of approvers allowed to decide reviews of a template.
+///
+/// Approving or rejecting a workflow of the template from PENDING_REVIEW
+/// or ESCALATED then also requires the caller to be in the pool, on top of
+/// the role the transition requires. Calling again replaces the pool; an
+/// empty list lets any approver decide.
+///
+/// # Arguments
+///
+/// * `template_hash` - The template the pool applies to
+/// * `approvers` - Accounts allowed to decide reviews
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+/// * `InvalidArgument` - An account is listed twice
+#[no_mangle]
+pub extern "C" fn set_template_approvers() {
+    let template_hash: [u8; 32] = runtime::get_named_arg("template_hash");
+    let approvers: Vec<AccountHash> = runtime::get_named_arg("approvers");
+    require_admin();
+    
+    for (index, approver) in approvers.iter().enumerate() {
+        if approvers[..index].contains(approver) {
+            runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+        }
+    }
+    
+    storage::dictionary_put(get_template_approvers_dict(), &hash_key(&template_hash), approvers);
+}
+
+/// Set how long terminal workflows of a template stay reopenable before
+/// they can be finalized.
+///
+/// Applies to workflows entering a terminal state afterwards.
+///
+/// # Arguments
+///
+/// * `template_hash` - The template the grace period applies to
+/// * `grace_seconds` - Grace period (0 completes workflows immediately)
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn set_terminal_grace() {
+    let template_hash: [u8; 32] = runtime::get_named_arg("template_hash");
+    let grace_seconds: u64 = runtime::get_named_arg("grace_seconds");
+    require_admin();
+    
+    storage::dictionary_put(get_terminal_grace_dict(), &hash_key(&template_hash), grace_seconds);
+}
+
+/// Set how long workflows of a template must wait after their last update
+/// before another transition is accepted, throttling rapid toggling that
+/// would bloat the history. Every caller-driven transition entry point
+/// applies it; `admin_override_state` does not.
+///
+/// # Arguments
+///
+/// * `template_hash` - The template the interval applies to
+/// * `interval_seconds` - Minimum interval (0 disables throttling)
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn set_min_transition_interval() {
+    let template_hash: [u8; 32] = runtime::get_named_arg("template_hash");
+    let interval_seconds: u64 = runtime::get_named_arg("interval_seconds");
+    require_admin();
+    
+    storage::dictionary_put(
+        get_min_transition_intervals_dict(),
+        &hash_key(&template_hash),
+        interval_seconds,
+    );
+}
+
+/// Register or update a custom state in the state catalog.
+///
+/// # Arguments
+///
+/// * `state` - Custom state ID (100 or above)
+/// * `name_hash` - Hash of the state name (off-chain reference)
+/// * `is_terminal` - Whether entering the state completes the workflow
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+/// * `InvalidArgument` - State is in the predefined range
+#[no_mangle]
+pub extern "C" fn register_state() {
+    let state: u8 = runtime::get_named_arg("state");
+    let name_hash: [u8; 32] = runtime::get_named_arg("name_hash");
+    let is_terminal: bool = runtime::get_named_arg("is_terminal");
+    require_admin();
+    
+    if state < states::CUSTOM_BASE {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    
+    storage::dictionary_put(get_state_catalog_dict(), &state.to_string(), (name_hash, is_terminal));
+}
+
+/// Assign a role mask to an account in the role registry.
+///
+/// # Arguments
+///
+/// * `account` - The account to assign roles to
+/// * `role_mask` - The full role mask to store (replaces any previous mask)
+///
+/// The account keeps its current ADMIN bit whatever the mask says: the bit
+/// tracks the admin key and only moves through `accept_admin`. The
+/// assignment is recorded in "role_changes" and the audit stream.
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+/// * `InvalidArgument` - Mask includes the override or system marker bit
+#[no_mangle]
+pub extern "C" fn assign_role() {
+    let account: AccountHash = runtime::get_named_arg("account");
+    let role_mask: u64 = runtime::get_named_arg("role_mask");
+    require_admin();
+    
+    let role_mask = assignable_mask(role_mask, read_role_mask(account)).unwrap_or_revert();
+    write_role_mask(account, role_mask, runtime::get_caller());
+}
+
+/// Compute the mask `assign_role` stores for an account currently holding
+/// `current`: the requested roles, with the ADMIN bit left as it was.
+fn assignable_mask(requested: u64, current: u64) -> Result<u64, WorkflowError> {
+    if requested & (roles::OVERRIDE_FLAG | roles::SYSTEM_FLAG) != 0 {
+        return Err(WorkflowError::InvalidArgument);
+    }
+    Ok((requested & !roles::ADMIN) | (current & roles::ADMIN))
+}
+
+/// Propose a successor to take over as admin.
+///
+/// The handoff only completes once the successor calls `accept_admin`, so
+/// a mistyped account cannot capture the contract. A new proposal replaces
+/// any earlier one.
+///
+/// # Arguments
+///
+/// * `successor` - The proposed new admin account
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn propose_admin() {
+    let successor: AccountHash = runtime::get_named_arg("successor");
+    require_admin();
+    
+    storage::write(get_pending_admin_uref(), Some(successor));
+}
+
+/// Check that `caller` is the proposed admin successor.
+fn check_admin_acceptance(
+    pending: Option<AccountHash>,
+    caller: AccountHash,
+) -> Result<(), WorkflowError> {
+    if pending != Some(caller) {
+        return Err(WorkflowError::InsufficientPermissions);
+    }
+    Ok(())
+}
+
+/// Accept a pending admin proposal, becoming the admin.
+///
+/// The ADMIN role moves from the previous admin to the caller.
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the proposed successor
+#[no_mangle]
+pub extern "C" fn accept_admin() {
+    let caller = runtime::get_caller();
+    let pending: Option<AccountHash> = storage::read(get_pending_admin_uref())
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(None);
+    check_admin_acceptance(pending, caller).unwrap_or_revert();
+    
+    let previous = read_admin();
+    write_role_mask(previous, read_role_mask(previous) & !roles::ADMIN, caller);
+    write_role_mask(caller, read_role_mask(caller) | roles::ADMIN, caller);
+    
+    storage::write(get_admin_uref(), caller);
+    storage::write(get_pending_admin_uref(), None::<AccountHash>);
+}
+
+/// Delegate some of the caller's roles to another account until an expiry.
+///
+/// Each account holds at most one delegation; a new call replaces it. The
+/// delegate may then perform transitions requiring the delegated roles.
+///
+/// # Arguments
+///
+/// * `delegate` - The account receiving authority
+/// * `expires_at` - Blocktime after which the delegation lapses
+/// * `role_mask` - Roles to delegate; must be held by the caller
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller does not hold every delegated role
+/// * `InvalidArgument` - Empty mask, delegation to self, or expiry in the past
+/// * `RateLimited` - Caller exhausted its call budget for the window
+#[no_mangle]
+pub extern "C" fn delegate_authority() {
+    consume_call_budget();
+    
+    let delegate: AccountHash = runtime::get_named_arg("delegate");
+    let expires_at: u64 = runtime::get_named_arg("expires_at");
+    let role_mask: u64 = runtime::get_named_arg("role_mask");
+    
+    let caller = runtime::get_caller();
+    let held = read_role_mask(caller);
+    check_delegation(caller, held, delegate, role_mask, expires_at, get_block_time())
+        .unwrap_or_revert();
+    
+    let delegations_dict = get_delegations_dict();
+    let delegates_dict = get_delegates_dict();
+    let caller_key = caller.to_string();
+    
+    // Drop the caller from a previous delegate's index
+    let previous: Option<(AccountHash, u64, u64)> =
+        storage::dictionary_get(delegations_dict, &caller_key)
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    if let Some((previous_delegate, _, _)) = previous {
+        let mut delegators = read_delegators(previous_delegate);
+        delegators.retain(|delegator| *delegator != caller);
+        storage::dictionary_put(delegates_dict, &previous_delegate.to_string(), delegators);
+    }
+    
+    let mut delegators = read_delegators(delegate);
+    delegators.push(caller);
+    storage::dictionary_put(delegates_dict, &delegate.to_string(), delegators);
+    storage::dictionary_put(delegations_dict, &caller_key, (delegate, expires_at, role_mask));
+}
+
+/// Register a decision category in the taxonomy.
+///
+/// # Arguments
+///
+/// * `category_id` - Non-zero category identifier
+/// * `name_hash` - Hash of the category name (off-chain reference)
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+/// * `InvalidArgument` - Category ID is zero
+#[no_mangle]
+pub extern "C" fn register_category() {
+    let category_id: u32 = runtime::get_named_arg("category_id");
+    let name_hash: [u8; 32] = runtime::get_named_arg("name_hash");
+    require_admin();
+    
+    // Zero is reserved for "no category"
+    if category_id == 0 {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    
+    storage::dictionary_put(get_categories_dict(), &category_id.to_string(), name_hash);
+}
+
+/// Toggle whether terminal transitions must supply a registered category.
+///
+/// # Arguments
+///
+/// * `required` - New flag value
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn set_category_required() {
+    let required: bool = runtime::get_named_arg("required");
+    require_admin();
+    
+    storage::write(get_category_required_uref(), required);
+}
+
+/// Toggle whether new workflows require intake approval.
+///
+/// # Arguments
+///
+/// * `enabled` - New flag value
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn set_intake_approval() {
+    let enabled: bool = runtime::get_named_arg("enabled");
+    require_admin();
+    
+    storage::write(get_intake_approval_uref(), enabled);
+}
+
+/// Toggle whether only REQUESTER accounts (directly or by delegation) may
+/// create workflows.
+///
+/// # Arguments
+///
+/// * `restricted` - New flag value; false leaves creation open to anyone
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn set_restricted_creation() {
+    let restricted: bool = runtime::get_named_arg("restricted");
+    require_admin();
+    
+    storage::write(get_restricted_creation_uref(), restricted);
+}
+
+/// Configure the per-account call budget for mutating entry points.
+///
+/// Each account may make at most `budget` mutating calls per window of
+/// `window_seconds`. Admin configuration calls are not counted.
+///
+/// # Arguments
+///
+/// * `budget` - Calls allowed per window; zero disables rate limiting
+/// * `window_seconds` - Length of a window in seconds
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+/// * `InvalidArgument` - Non-zero budget with a zero-length window
+#[no_mangle]
+pub extern "C" fn set_rate_limit() {
+    let budget: u32 = runtime::get_named_arg("budget");
+    let window_seconds: u64 = runtime::get_named_arg("window_seconds");
+    require_admin();
+    
+    if budget != 0 && window_seconds == 0 {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    
+    storage::write(get_rate_limit_uref(), (budget, window_seconds));
+}
+
+/// Set the maximum length of comment text stored on-chain.
+///
+/// # Arguments
+///
+/// * `max_len` - Maximum comment length in bytes
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn set_comment_max_len() {
+    let max_len: u32 = runtime::get_named_arg("max_len");
+    require_admin();
+    
+    storage::write(get_comment_max_len_uref(), max_len);
+}
+
+/// Set the maximum number of history records a workflow may accumulate.
+///
+/// # Arguments
+///
+/// * `max_len` - Maximum records per workflow
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn set_max_history_len() {
+    let max_len: u32 = runtime::get_named_arg("max_len");
+    require_admin();
+    
+    storage::write(get_max_history_len_uref(), max_len);
+}
+
+/// Set the maximum number of times a workflow may be escalated.
+///
+/// # Arguments
+///
+/// * `max_escalations` - Maximum escalations per workflow (0 disables
+///   escalation)
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn set_max_escalations() {
+    let max_escalations: u8 = runtime::get_named_arg("max_escalations");
+    require_admin();
+    
+    storage::write(get_max_escalations_uref(), max_escalations);
+}
+
+/// Pause the contract, blocking workflow creation, transitions and votes.
+/// Read-only entry points keep working.
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn pause_contract() {
+    require_admin();
+    storage::write(get_paused_uref(), true);
+}
+
+/// Resume normal operation after a pause.
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn unpause_contract() {
+    require_admin();
+    storage::write(get_paused_uref(), false);
+}
+
+/// Emit a liveness heartbeat for external monitoring.
+///
+/// # Events
+///
+/// `heartbeat` with the current blocktime, workflow count and contract version.
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn heartbeat() {
+    require_admin();
+    
+    emit_event(
+        "heartbeat",
+        &[
+            ("timestamp", get_block_time().to_string()),
+            ("workflow_count", read_workflow_count().to_string()),
+            ("contract_version", CONTRACT_VERSION.to_string()),
+        ],
+    );
+}
+
+/// The highest countable ID among `count` IDs from `start_id` for which
+/// `in_use` holds, given the current `workflow_count`.
+fn highest_countable_in_use<F: Fn(U256) -> bool>(
+    start_id: U256,
+    count: u64,
+    workflow_count: U256,
+    in_use: F,
+) -> Option<U256> {
+    let mut highest: Option<U256> = None;
+    let mut workflow_id = start_id;
+    for _ in 0..count {
+        if is_countable_id(workflow_id, workflow_count) && in_use(workflow_id) {
+            highest = Some(workflow_id);
+        }
+        workflow_id = workflow_id.saturating_add(U256::one());
+    }
+    highest
+}
+
+/// Raise the workflow count to the highest ID in use within a range.
+///
+/// Repairs a count left below a caller-supplied ID, so range scans over
+/// 1..=workflow_count see every workflow. Archived workflows count as in
+/// use. The count never decreases. Derived IDs are too sparse to scan for
+/// and stay outside the count (see `create_workflow`); IDs more than 100
+/// above the count are ignored for the same reason.
+///
+/// # Arguments
+///
+/// * `start_id` - First workflow ID in the range
+/// * `count` - Number of IDs to scan (capped at 100)
+///
+/// # Returns
+///
+/// The workflow count after reconciling
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+#[no_mangle]
+pub extern "C" fn reconcile_count() {
+    let start_id: U256 = runtime::get_named_arg("start_id");
+    let count: u64 = runtime::get_named_arg("count");
+    require_admin();
+    
+    let highest = highest_countable_in_use(
+        start_id,
+        count.min(MAX_PAGE_SIZE),
+        read_workflow_count(),
+        |workflow_id| is_workflow_id_in_use(&workflow_id.to_string()),
+    );
+    
+    if let Some(highest) = highest {
+        raise_workflow_count(highest);
+    }
+    
+    return_value(read_workflow_count());
+}
+
+/// Transfer an open workflow to a new creator, e.g. a successor when an
+/// employee leaves.
+///
+/// Moves the workflow between the creators' entries in "creator_index" and
+/// records the reassignment in "creator_changes" and the audit stream. The
+/// first reassignment keeps the creator at creation in "original_creators",
+/// so `verify_integrity` still checks the genesis commitment.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to reassign
+/// * `new_creator` - Account taking over the workflow
+///
+/// # Events
+///
+/// `reassign` with the previous and new creator.
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+/// * `WorkflowNotFound` - Workflow does not exist
+/// * `WorkflowVoided` - Workflow has been voided
+/// * `WorkflowAlreadyCompleted` - Workflow is in a terminal state
+/// * `InvalidArgument` - New creator is already the creator
+/// * `ContractPaused` - Contract is paused
+#[no_mangle]
+pub extern "C" fn reassign_creator() {
+    require_not_paused();
+    
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let new_creator: AccountHash = runtime::get_named_arg("new_creator");
+    require_admin();
+    
+    let caller = runtime::get_caller();
+    let timestamp = get_block_time();
+    
+    let key = workflow_id.to_string();
+    let mut workflow = load_workflow(&key);
+    require_not_voided(&workflow);
+    
+    if workflow.is_completed
+        || is_template_terminal_state(&workflow.template_hash, workflow.current_state)
+    {
+        runtime::revert(ApiError::User(WorkflowError::WorkflowAlreadyCompleted as u16));
+    }
+    
+    let old_creator = workflow.creator;
+    if new_creator == old_creator {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    
+    // Keep the creator the genesis hash commits to
+    if read_original_creator(&workflow) == old_creator {
+        storage::dictionary_put(get_original_creators_dict(), &key, old_creator);
+    }
+    
+    let mut old_created = read_creator_workflows(old_creator);
+    let mut new_created = read_creator_workflows(new_creator);
+    move_index_entry(&mut old_created, &mut new_created, workflow_id);
+    storage::dictionary_put(get_creator_index_dict(), &old_creator.to_string(), old_created);
+    storage::dictionary_put(get_creator_index_dict(), &new_creator.to_string(), new_created);
+    
+    workflow.creator = new_creator;
+    storage::dictionary_put(get_workflows_dict(), &key, workflow);
+    
+    record_logged_change(
+        audit_kinds::CREATOR_CHANGE,
+        get_creator_changes_dict(),
+        (workflow_id, (old_creator, new_creator), (caller, timestamp)),
+    );
+    
+    emit_event(
+        "reassign",
+        &[
+            ("workflow_id", workflow_id.to_string()),
+            ("old_creator", old_creator.to_string()),
+            ("new_creator", new_creator.to_string()),
+            ("actor", caller.to_string()),
+            ("timestamp", timestamp.to_string()),
+        ],
+    );
+}
+
+/// Number of leading records older than `cutoff_timestamp` that may be
+/// purged. The most recent record is never purged.
+fn purge_count(transitions: &[TransitionRecord], cutoff_timestamp: u64) -> usize {
+    transitions
+        .iter()
+        .take(transitions.len().saturating_sub(1))
+        .take_while(|transition| transition.timestamp < cutoff_timestamp)
+        .count()
+}
+
+/// Remove the transition records of a workflow older than a cutoff, for
+/// retention policies.
+///
+/// Records are removed from the front of the history with their stored
+/// comments; the most recent record is always kept, as is the current
+/// state. Later records keep chaining to the purged ones: the count and
+/// the hash of the last purged record are kept in "history_purges", so
+/// `verify_chain` still passes and comment keys stay stable, while history
+/// indices become relative to the retained records. The purge is recorded
+/// in "purge_log" and the audit stream. Earlier global state still holds
+/// the purged records, as with any overwritten value on Casper.
+///
+/// # Arguments
+///
+/// * `workflow_id` - The workflow to purge
+/// * `cutoff_timestamp` - Block time (ms); records with an earlier
+///   timestamp are removed
+///
+/// # Returns
+///
+/// Number of records removed (zero leaves everything untouched)
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+/// * `WorkflowNotFound` - Workflow does not exist
+#[no_mangle]
+pub extern "C" fn purge_history_before() {
+    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    let cutoff_timestamp: u64 = runtime::get_named_arg("cutoff_timestamp");
+    require_admin();
+    
+    let key = workflow_id.to_string();
+    load_workflow(&key);
+    
+    let mut transitions: Vec<TransitionRecord> =
+        storage::dictionary_get(get_transitions_dict(), &key)
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+            .unwrap_or_default();
+    
+    let count = purge_count(&transitions, cutoff_timestamp);
+    if count == 0 {
+        return_value(0u64);
+    }
+    
+    // Blank the comments of the purged records before their keys shift
+    let comments_dict = get_comments_dict();
+    for index in 0..count as u64 {
+        let entry_key = comment_key(workflow_id, index);
+        let comment: Option<String> = storage::dictionary_get(comments_dict, &entry_key)
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+        if comment.is_some() {
+            storage::dictionary_put(comments_dict, &entry_key, String::new());
+        }
+    }
+    
+    let (purged, _) = read_history_purge(workflow_id);
+    let anchor = transition_hash(&transitions[count - 1]);
+    let purged = purged
+        .checked_add(count as u64)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::Overflow as u16));
+    storage::dictionary_put(get_history_purges_dict(), &key, (purged, anchor));
+    
+    transitions.drain(..count);
+    storage::dictionary_put(get_transitions_dict(), &key, transitions);
+    
+    record_logged_change(
+        audit_kinds::HISTORY_PURGE,
+        get_purge_log_dict(),
+        (
+            workflow_id,
+            (cutoff_timestamp, count as u64),
+            (runtime::get_caller(), get_block_time()),
+        ),
+    );
+    
+    return_value(count as u64);
+}
+
+/// Refresh the global Merkle root.
+///
+/// Recomputes the audit digests of up to `limit` workflows starting at
+/// `start_id` and updates only the tree nodes on their paths to the root,
+/// so each sweep costs O(limit * log n) rather than a rebuild over every
+/// leaf. The tree grows as sweeps reach new IDs; a sweep must start at or
+/// before the first ID the tree does not cover yet, so no ID is skipped.
+/// Large contracts are refreshed by sweeping successive ranges. Derived IDs
+/// lie above the workflow count and are not covered.
+///
+/// # Arguments
+///
+/// * `start_id` - First workflow ID to refresh (at most the tree size + 1)
+/// * `limit` - Maximum number of workflows to refresh (capped at the page size)
+///
+/// # Returns
+///
+/// The new 32-byte root
+///
+/// # Errors
+///
+/// * `InsufficientPermissions` - Caller is not the admin
+/// * `InvalidArgument` - `start_id` would leave a gap after the covered IDs
+#[no_mangle]
+pub extern "C" fn update_global_root() {
+    require_admin();
+    
+    let start_id: U256 = runtime::get_named_arg("start_id");
+    let limit: u64 = runtime::get_named_arg("limit");
+    
+    let count = read_workflow_count();
+    let size_uref = get_global_tree_size_uref();
+    let size: u64 = storage::read(size_uref)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+        .unwrap_or(0);
+    
+    // Sweeps extend the tree contiguously, so every leaf has a stored path
+    let start_id = start_id.max(U256::one());
+    if start_id > U256::from(size) + U256::one() {
+        runtime::revert(ApiError::User(WorkflowError::InvalidArgument as u16));
+    }
+    let limit = limit.min(MAX_PAGE_SIZE);
+    let end_id = start_id.saturating_add(U256::from(limit)).min(count + U256::one());
+    if limit == 0 || start_id >= end_id {
+        let root: [u8; 32] = storage::read(get_global_root_uref())
+            .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+            .unwrap_or([0u8; 32]);
+        return_value(root);
+    }
+    
+    // Refresh the digests in the requested range
+    let leaves_dict = get_global_leaves_dict();
+    let mut workflow_id = start_id;
+    while workflow_id < end_id {
+        let key = workflow_id.to_string();
+        if let Some(digest) = workflow_digest(&key) {
+            storage::dictionary_put(leaves_dict, &key, digest);
+        }
+        workflow_id += U256::one();
+    }
+    
+    // Leaves sit at position workflow_id - 1
+    let lo = u64::try_from(start_id - U256::one())
+        .ok()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::Overflow as u16));
+    let hi = lo + (end_id - start_id).as_u64() - 1;
+    let size = size.max(hi + 1);
+    
+    let (nodes, root) = merkle_update(size, lo, hi, read_global_node);
+    let nodes_dict = get_global_nodes_dict();
+    for (level, index, node) in nodes {
+        storage::dictionary_put(nodes_dict, &format!("{}:{}", level, index), node);
+    }
+    storage::write(size_uref, size);
+    storage::write(get_global_root_uref(), root);
+    
+    return_value(root);
+}
+
+// =============================================================================
+// Contract Installation
+// =============================================================================
+
+/// Build the contract's entry points. Entry points that change workflow
+/// records on behalf of a caller use `mutation_access`; reads and admin
+/// entry points stay public, as admin calls guard themselves.
+fn build_entry_points(mutation_access: EntryPointAccess) -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    
+    // create_workflow - Casper 2.0 uses EntryPointType::Called
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_workflow",
+        vec![
+            Parameter::new("template_hash", CLType::ByteArray(32)),
+            Parameter::new("data_hash", CLType::ByteArray(32)),
+            Parameter::new("hash_algo", CLType::U8),
+            Parameter::new("sla_seconds", CLType::U64),
+            Parameter::new("required_approvals", CLType::U8),
+            Parameter::new("use_derived_id", CLType::Bool),
+            Parameter::new("nonce", CLType::U64),
+        ],
+        CLType::U256,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // create_workflow_with_id - idempotent creation under a caller ID
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_workflow_with_id",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("template_hash", CLType::ByteArray(32)),
+            Parameter::new("data_hash", CLType::ByteArray(32)),
+            Parameter::new("hash_algo", CLType::U8),
+            Parameter::new("sla_seconds", CLType::U64),
+            Parameter::new("required_approvals", CLType::U8),
+        ],
+        CLType::U256,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // create_workflow_in_state - admin-only import of legacy workflows
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_workflow_in_state",
+        vec![
+            Parameter::new("template_hash", CLType::ByteArray(32)),
+            Parameter::new("data_hash", CLType::ByteArray(32)),
+            Parameter::new("state", CLType::U8),
+            Parameter::new("external_ref", CLType::ByteArray(32)),
+            Parameter::new("hash_algo", CLType::U8),
+            Parameter::new("sla_seconds", CLType::U64),
+            Parameter::new("required_approvals", CLType::U8),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // create_workflows_batch - bulk onboarding
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_workflows_batch",
+        vec![
+            Parameter::new("template_hashes", CLType::List(Box::new(CLType::ByteArray(32)))),
+            Parameter::new("data_hashes", CLType::List(Box::new(CLType::ByteArray(32)))),
+            Parameter::new("hash_algo", CLType::U8),
+        ],
+        CLType::List(Box::new(CLType::U256)),
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // transition_state
+    entry_points.add_entry_point(EntryPoint::new(
+        "transition_state",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("to_state", CLType::U8),
+            Parameter::new("actor_role", CLType::U64),
+            Parameter::new("comment_hash", CLType::ByteArray(32)),
+            Parameter::new("category_id", CLType::U32),
+            Parameter::new("request_id", CLType::ByteArray(32)),
+            Parameter::new("external_ref", CLType::ByteArray(32)),
+            Parameter::new("confidential", CLType::Bool),
+            Parameter::new("comment", CLType::String),
+            Parameter::new("reason_code", CLType::U32),
+            Parameter::new("hash_algo", CLType::U8),
+            Parameter::new("condition_hash", CLType::ByteArray(32)),
+        ],
+        CLType::U8,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // transition_batch - same transition across many workflows
+    entry_points.add_entry_point(EntryPoint::new(
+        "transition_batch",
+        vec![
+            Parameter::new("workflow_ids", CLType::List(Box::new(CLType::U256))),
+            Parameter::new("to_state", CLType::U8),
+            Parameter::new("actor_role", CLType::U64),
+            Parameter::new("comment_hash", CLType::ByteArray(32)),
+            Parameter::new("atomic", CLType::Bool),
+            Parameter::new("reason_code", CLType::U32),
+            Parameter::new("hash_algo", CLType::U8),
+        ],
+        CLType::List(Box::new(<(U256, u32)>::cl_type())),
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // cast_approval - quorum vote towards PENDING_REVIEW -> APPROVED
+    entry_points.add_entry_point(EntryPoint::new(
+        "cast_approval",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("actor_role", CLType::U64),
+            Parameter::new("comment_hash", CLType::ByteArray(32)),
+            Parameter::new("category_id", CLType::U32),
+            Parameter::new("request_id", CLType::ByteArray(32)),
+            Parameter::new("external_ref", CLType::ByteArray(32)),
+            Parameter::new("confidential", CLType::Bool),
+            Parameter::new("comment", CLType::String),
+            Parameter::new("reason_code", CLType::U32),
+            Parameter::new("hash_algo", CLType::U8),
+            Parameter::new("condition_hash", CLType::ByteArray(32)),
+        ],
+        CLType::U8,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // reopen_workflow - creator moves a REJECTED workflow back to DRAFT
+    entry_points.add_entry_point(EntryPoint::new(
+        "reopen_workflow",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("comment_hash", CLType::ByteArray(32)),
+        ],
+        CLType::Unit,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // finalize - completes a terminal workflow after its grace period
+    entry_points.add_entry_point(EntryPoint::new(
+        "finalize",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::Unit,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // admin_override_state - admin-only break-glass state correction
+    entry_points.add_entry_point(EntryPoint::new(
+        "admin_override_state",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("to_state", CLType::U8),
+            Parameter::new("comment_hash", CLType::ByteArray(32)),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // escalate_to - escalation assigned to a named senior approver
+    entry_points.add_entry_point(EntryPoint::new(
+        "escalate_to",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("assignee", CLType::ByteArray(32)),
+            Parameter::new("comment_hash", CLType::ByteArray(32)),
+            Parameter::new("condition_hash", CLType::ByteArray(32)),
+        ],
+        CLType::Unit,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // auto_escalate - anyone may escalate an overdue review
+    entry_points.add_entry_point(EntryPoint::new(
+        "auto_escalate",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("comment_hash", CLType::ByteArray(32)),
+            Parameter::new("condition_hash", CLType::ByteArray(32)),
+        ],
+        CLType::Unit,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // withdraw_to_draft - creator pulls a submission back to DRAFT
+    entry_points.add_entry_point(EntryPoint::new(
+        "withdraw_to_draft",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("comment_hash", CLType::ByteArray(32)),
+        ],
+        CLType::Unit,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // update_data_hash - creator revises business data while in DRAFT
+    entry_points.add_entry_point(EntryPoint::new(
+        "update_data_hash",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("data_hash", CLType::ByteArray(32)),
+        ],
+        CLType::Unit,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // set_metadata - creator attaches a named document hash
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_metadata",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("attr_name", CLType::String),
+            Parameter::new("value", CLType::ByteArray(32)),
+        ],
+        CLType::Unit,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // get_metadata
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_metadata",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("attr_name", CLType::String),
+        ],
+        CLType::Option(Box::new(CLType::ByteArray(32))),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // void_workflow - admin-only soft delete
+    entry_points.add_entry_point(EntryPoint::new(
+        "void_workflow",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // archive_workflow - moves a completed workflow out of the active set
+    entry_points.add_entry_point(EntryPoint::new(
+        "archive_workflow",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::Unit,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // store_comment - on-chain text for a transition's comment_hash
+    entry_points.add_entry_point(EntryPoint::new(
+        "store_comment",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("index", CLType::U64),
+            Parameter::new("comment", CLType::String),
+        ],
+        CLType::Unit,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // get_comment
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_comment",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("index", CLType::U64),
+        ],
+        CLType::Option(Box::new(CLType::String)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_workflow_state
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_workflow_state",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::Any,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // verify_integrity - recheck the creation commitment
+    entry_points.add_entry_point(EntryPoint::new(
+        "verify_integrity",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_workflow_summary - compact list-view row
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_workflow_summary",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        WorkflowSummary::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_pending_approvals - quorum progress
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_pending_approvals",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        <(Vec<AccountHash>, u8)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_archived_workflow
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_archived_workflow",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::Any,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_archived_history
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_archived_history",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::Any,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_workflow_history
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_workflow_history",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::Any,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_data_changes - data provenance apart from the state history
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_data_changes",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::List(Box::new(DataChangeRecord::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_transition_at - single history record
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_transition_at",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("index", CLType::U64),
+        ],
+        TransitionRecord::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_transition_count
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_transition_count",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_workflow_history_paged
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_workflow_history_paged",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("offset", CLType::U64),
+            Parameter::new("limit", CLType::U64),
+        ],
+        <(Vec<TransitionRecord>, u64)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_histories_batch - histories of several workflows, size-bounded
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_histories_batch",
+        vec![Parameter::new("workflow_ids", CLType::List(Box::new(CLType::U256)))],
+        <(Vec<(U256, Vec<TransitionRecord>)>, bool)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_workflow_history_filtered - history narrowed by actor and states
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_workflow_history_filtered",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("actor", CLType::ByteArray(32)),
+            Parameter::new("from_state", CLType::U8),
+            Parameter::new("to_state", CLType::U8),
+        ],
+        CLType::List(Box::new(TransitionRecord::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // verify_chain
+    entry_points.add_entry_point(EntryPoint::new(
+        "verify_chain",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // validate_transition - dry-run returning the would-be error code
+    entry_points.add_entry_point(EntryPoint::new(
+        "validate_transition",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("to_state", CLType::U8),
+            Parameter::new("actor_role", CLType::U64),
+        ],
+        CLType::U32,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // can_transition - per-account permission check for UIs
+    entry_points.add_entry_point(EntryPoint::new(
+        "can_transition",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("to_state", CLType::U8),
+            Parameter::new("account", CLType::ByteArray(32)),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // is_creator - creator check without returning the workflow
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_creator",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("account", CLType::ByteArray(32)),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // required_role_for - roles needed for a transition, for UIs
+    entry_points.add_entry_point(EntryPoint::new(
+        "required_role_for",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("to_state", CLType::U8),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // diagnose_transition - read-only explanation of a failing transition
+    entry_points.add_entry_point(EntryPoint::new(
+        "diagnose_transition",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("to_state", CLType::U8),
+            Parameter::new("actor_role", CLType::U64),
+        ],
+        <(u32, U256, u8)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // is_terminal_transition
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_terminal_transition",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("to_state", CLType::U8),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_state_path
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_state_path",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::List(Box::new(CLType::U8)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_avg_time_in_state
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_avg_time_in_state",
+        vec![
+            Parameter::new("state", CLType::U8),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // check_overdue
+    entry_points.add_entry_point(EntryPoint::new(
+        "check_overdue",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_workflows_past_deadline
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_workflows_past_deadline",
+        vec![
+            Parameter::new("now", CLType::U64),
+            Parameter::new("limit", CLType::U64),
+        ],
+        CLType::List(Box::new(CLType::U256)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_escalated_for - senior approver worklist
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_escalated_for",
+        vec![
+            Parameter::new("assignee", CLType::ByteArray(32)),
+        ],
+        CLType::List(Box::new(CLType::U256)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_count_for_state - per-state tally for dashboards
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_count_for_state",
+        vec![
+            Parameter::new("state", CLType::U8),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_transition_type_count - contract-wide decision outcome tally
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_transition_type_count",
+        vec![
+            Parameter::new("to_state", CLType::U8),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_workflows_in_state - per-state worklist
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_workflows_in_state",
+        vec![
+            Parameter::new("state", CLType::U8),
+            Parameter::new("offset", CLType::U64),
+            Parameter::new("limit", CLType::U64),
+        ],
+        CLType::List(Box::new(CLType::U256)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_workflows_range - bulk export by ID range
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_workflows_range",
+        vec![
+            Parameter::new("start_id", CLType::U256),
+            Parameter::new("count", CLType::U64),
+        ],
+        CLType::Any,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_overdue_workflows - overdue scan over an ID range
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_overdue_workflows",
+        vec![
+            Parameter::new("start_id", CLType::U256),
+            Parameter::new("count", CLType::U64),
+        ],
+        CLType::List(Box::new(CLType::U256)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_workflows_by_creator - secondary index lookup
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_workflows_by_creator",
+        vec![
+            Parameter::new("creator", CLType::ByteArray(32)),
+            Parameter::new("offset", CLType::U64),
+            Parameter::new("limit", CLType::U64),
+        ],
+        CLType::List(Box::new(CLType::U256)),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_audit_stream - transitions and role changes in sequence order
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_audit_stream",
+        vec![
+            Parameter::new("after_seq", CLType::U64),
+            Parameter::new("limit", CLType::U64),
+        ],
+        CLType::List(Box::new(<(u8, U256)>::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_role_change
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_role_change",
+        vec![
+            Parameter::new("seq", CLType::U64),
+        ],
+        <(AccountHash, u64, (AccountHash, u64))>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_workflow_count
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_workflow_count",
+        vec![],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_total_transitions
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_total_transitions",
+        vec![],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_template_count
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_template_count",
+        vec![
+            Parameter::new("template_hash", CLType::ByteArray(32)),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_deployed_at
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_deployed_at",
+        vec![],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_contract_version
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_contract_version",
+        vec![],
+        <(String, u8)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_contract_info - health summary for monitoring
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_contract_info",
+        vec![],
+        <((String, u8), (U256, U256), bool)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_schema - record byte layouts for off-chain decoders
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_schema",
+        vec![],
+        <Vec<(String, u8, Vec<String>)>>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_package_hash - package lookup for upgrade tooling
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_package_hash",
+        vec![],
+        CLType::ByteArray(32),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // get_global_root - Merkle commitment over all workflows
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_global_root",
+        vec![],
+        CLType::ByteArray(32),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // register_compliance_proof - stores proof hash for approved workflows
+    entry_points.add_entry_point(EntryPoint::new(
+        "register_compliance_proof",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("proof_hash", CLType::ByteArray(32)),
+        ],
+        CLType::Unit,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // get_compliance_proof - retrieves proof hash for a workflow
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_compliance_proof",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+        ],
+        CLType::ByteArray(32),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // define_transition_rules - admin-only per-template state machine
+    entry_points.add_entry_point(EntryPoint::new(
+        "define_transition_rules",
+        vec![
+            Parameter::new("template_hash", CLType::ByteArray(32)),
+            Parameter::new("rules", Vec::<TransitionRule>::cl_type()),
+            Parameter::new("allow_self_approval", CLType::Bool),
+            Parameter::new("comment_required_states", CLType::List(Box::new(CLType::U8))),
+            Parameter::new("rule_conditions", Vec::<RuleCondition>::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // set_state_sla - admin-only per-template SLA configuration
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_state_sla",
+        vec![
+            Parameter::new("template_hash", CLType::ByteArray(32)),
+            Parameter::new("state", CLType::U8),
+            Parameter::new("sla_seconds", CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // set_terminal_states - admin-only per-template terminal states
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_terminal_states",
+        vec![
+            Parameter::new("template_hash", CLType::ByteArray(32)),
+            Parameter::new("terminal_states", CLType::List(Box::new(CLType::U8))),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // set_template_approvers - admin-only per-template approver pool
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_template_approvers",
+        vec![
+            Parameter::new("template_hash", CLType::ByteArray(32)),
+            Parameter::new("approvers", CLType::List(Box::new(CLType::ByteArray(32)))),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // set_terminal_grace - admin-only per-template appeal window
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_terminal_grace",
+        vec![
+            Parameter::new("template_hash", CLType::ByteArray(32)),
+            Parameter::new("grace_seconds", CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // set_min_transition_interval - admin-only per-template throttle
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_min_transition_interval",
+        vec![
+            Parameter::new("template_hash", CLType::ByteArray(32)),
+            Parameter::new("interval_seconds", CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // register_state - admin-only custom state catalog
+    entry_points.add_entry_point(EntryPoint::new(
+        "register_state",
+        vec![
+            Parameter::new("state", CLType::U8),
+            Parameter::new("name_hash", CLType::ByteArray(32)),
+            Parameter::new("is_terminal", CLType::Bool),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // assign_role - admin-only role registry management
+    entry_points.add_entry_point(EntryPoint::new(
+        "assign_role",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("role_mask", CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // propose_admin - first step of the admin handoff
+    entry_points.add_entry_point(EntryPoint::new(
+        "propose_admin",
+        vec![
+            Parameter::new("successor", CLType::ByteArray(32)),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // accept_admin - successor completes the admin handoff
+    entry_points.add_entry_point(EntryPoint::new(
+        "accept_admin",
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // delegate_authority - time-limited delegation of the caller's roles
+    entry_points.add_entry_point(EntryPoint::new(
+        "delegate_authority",
+        vec![
+            Parameter::new("delegate", CLType::ByteArray(32)),
+            Parameter::new("expires_at", CLType::U64),
+            Parameter::new("role_mask", CLType::U64),
+        ],
+        CLType::Unit,
+        mutation_access.clone(),
+        EntryPointType::Called,
+    ).into());
+    
+    // register_category - adds a decision category to the taxonomy
+    entry_points.add_entry_point(EntryPoint::new(
+        "register_category",
+        vec![
+            Parameter::new("category_id", CLType::U32),
+            Parameter::new("name_hash", CLType::ByteArray(32)),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // set_category_required - toggles category enforcement on terminal transitions
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_category_required",
+        vec![
+            Parameter::new("required", CLType::Bool),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // set_intake_approval - toggles maker-checker on workflow creation
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_intake_approval",
+        vec![
+            Parameter::new("enabled", CLType::Bool),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // set_restricted_creation - limits workflow creation to requesters
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_restricted_creation",
+        vec![
+            Parameter::new("restricted", CLType::Bool),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // set_rate_limit - admin-only per-account call budget
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_rate_limit",
+        vec![
+            Parameter::new("budget", CLType::U32),
+            Parameter::new("window_seconds", CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // set_comment_max_len - admin-only limit on stored comment text
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_comment_max_len",
+        vec![
+            Parameter::new("max_len", CLType::U32),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // set_max_history_len - admin-only cap on history records per workflow
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_max_history_len",
+        vec![
+            Parameter::new("max_len", CLType::U32),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // set_max_escalations - admin-only cap on escalations per workflow
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_max_escalations",
+        vec![
+            Parameter::new("max_escalations", CLType::U8),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // pause_contract - admin-only emergency stop
+    entry_points.add_entry_point(EntryPoint::new(
+        "pause_contract",
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // unpause_contract - admin-only resume
+    entry_points.add_entry_point(EntryPoint::new(
+        "unpause_contract",
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // heartbeat - liveness signal for monitoring
+    entry_points.add_entry_point(EntryPoint::new(
+        "heartbeat",
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // update_global_root - admin-only sweep refreshing the Merkle root
+    entry_points.add_entry_point(EntryPoint::new(
+        "update_global_root",
+        vec![
+            Parameter::new("start_id", CLType::U256),
+            Parameter::new("limit", CLType::U64),
+        ],
+        CLType::ByteArray(32),
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // reconcile_count - admin-only repair of the workflow count
+    entry_points.add_entry_point(EntryPoint::new(
+        "reconcile_count",
+        vec![
+            Parameter::new("start_id", CLType::U256),
+            Parameter::new("count", CLType::U64),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // reassign_creator - admin-only transfer of an open workflow
+    entry_points.add_entry_point(EntryPoint::new(
+        "reassign_creator",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("new_creator", CLType::ByteArray(32)),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    // purge_history_before - admin-only retention of old history records
+    entry_points.add_entry_point(EntryPoint::new(
+        "purge_history_before",
+        vec![
+            Parameter::new("workflow_id", CLType::U256),
+            Parameter::new("cutoff_timestamp", CLType::U64),
+        ],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Called,
+    ).into());
+    
+    entry_points
+}
+
+/// Register an access group on the contract package, returning a URef
+/// that grants its holder membership.
+fn set_entry_group(package_hash: ContractPackageHash, label: &str) -> URef {
+    storage::create_contract_user_group(package_hash, label, 1, BTreeSet::new())
+        .unwrap_or_revert()
+        .pop()
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
+}
+
+/// Contract entry point for installation.
+/// Sets up named keys and entry points.
+///
+/// # Arguments
+///
+/// * `gate_mutations` - Optional flag (default false) restricting the
+///   non-admin entry points that create, transition, annotate, archive or
+///   delegate on workflows to the "mutators" access group; read and admin
+///   entry points stay public. The installer receives the group's first
+///   URef under "workflow_mutation_access".
+/// * `enable_events` - Optional flag (default true); false registers no
+///   message topic and turns every event into a no-op
+/// * `events_topic` - Optional message topic name (default "workflow_events")
+///
+/// # Group membership
 ///
-/// State changes are verifiable via RPC queries and Casper Explorer.
-/// Sidecar event indexing available for production deployments.
+/// Membership is a matter of URef distribution rather than contract state.
+/// To add a member, the holder of the package access URef
+/// ("workflow_contract_access", kept by the installer) runs session code
+/// calling `storage::provision_contract_user_group_uref` for "mutators"
+/// and hands the returned URef to the new member, who must keep it in its
+/// account's named keys. `storage::remove_contract_user_group_urefs`
+/// revokes a member.
 #[no_mangle]
-pub extern "C" fn create_workflow() {
-    // Get arguments
-    let template_hash: [u8; 32] = runtime::get_named_arg("template_hash");
-    let data_hash: [u8; 32] = runtime::get_named_arg("data_hash");
+pub extern "C" fn call() {
+    let gate_mutations: bool = runtime::try_get_named_arg("gate_mutations").unwrap_or(false);
+    let enable_events: bool = runtime::try_get_named_arg("enable_events").unwrap_or(true);
+    let events_topic: Option<String> = if enable_events {
+        Some(runtime::try_get_named_arg("events_topic").unwrap_or_else(|| EVENTS_TOPIC.into()))
+    } else {
+        None
+    };
     
-    // Get caller information
-    let caller = runtime::get_caller();
-    let timestamp = get_block_time();
+    // Create dictionaries for storage
+    let workflows_dict = storage::new_dictionary(WORKFLOWS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let transitions_dict = storage::new_dictionary(TRANSITIONS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let data_changes_dict = storage::new_dictionary(DATA_CHANGES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let compliance_proofs_dict = storage::new_dictionary(COMPLIANCE_PROOFS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let workflows_archive_dict = storage::new_dictionary(WORKFLOWS_ARCHIVE_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let transitions_archive_dict = storage::new_dictionary(TRANSITIONS_ARCHIVE_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let categories_dict = storage::new_dictionary(CATEGORIES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let creator_index_dict = storage::new_dictionary(CREATOR_INDEX_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let template_counts_dict = storage::new_dictionary(TEMPLATE_COUNTS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let comments_dict = storage::new_dictionary(COMMENTS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let metadata_dict = storage::new_dictionary(METADATA_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let metadata_changes_dict = storage::new_dictionary(METADATA_CHANGES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let creator_changes_dict = storage::new_dictionary(CREATOR_CHANGES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let original_creators_dict = storage::new_dictionary(ORIGINAL_CREATORS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let history_purges_dict = storage::new_dictionary(HISTORY_PURGES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let purge_log_dict = storage::new_dictionary(PURGE_LOG_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let escalation_index_dict = storage::new_dictionary(ESCALATION_INDEX_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let roles_dict = storage::new_dictionary(ROLES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let delegations_dict = storage::new_dictionary(DELEGATIONS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let delegates_dict = storage::new_dictionary(DELEGATES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let transition_rules_dict = storage::new_dictionary(TRANSITION_RULES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let self_approval_dict = storage::new_dictionary(SELF_APPROVAL_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let comment_required_dict = storage::new_dictionary(COMMENT_REQUIRED_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let rule_conditions_dict = storage::new_dictionary(RULE_CONDITIONS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let template_approvers_dict = storage::new_dictionary(TEMPLATE_APPROVERS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let seen_requests_dict = storage::new_dictionary(SEEN_REQUESTS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let time_in_state_dict = storage::new_dictionary(TIME_IN_STATE_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let state_slas_dict = storage::new_dictionary(STATE_SLAS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let terminal_states_dict = storage::new_dictionary(TERMINAL_STATES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let terminal_grace_dict = storage::new_dictionary(TERMINAL_GRACE_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let min_transition_intervals_dict = storage::new_dictionary(MIN_TRANSITION_INTERVALS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let pending_approvals_dict = storage::new_dictionary(PENDING_APPROVALS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let state_catalog_dict = storage::new_dictionary(STATE_CATALOG_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let state_counts_dict = storage::new_dictionary(STATE_COUNTS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let transition_type_counts_dict = storage::new_dictionary(TRANSITION_TYPE_COUNTS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let state_index_dict = storage::new_dictionary(STATE_INDEX_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let global_leaves_dict = storage::new_dictionary(GLOBAL_LEAVES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let global_nodes_dict = storage::new_dictionary(GLOBAL_NODES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let audit_stream_dict = storage::new_dictionary(AUDIT_STREAM_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let role_changes_dict = storage::new_dictionary(ROLE_CHANGES_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let call_budget_dict = storage::new_dictionary(CALL_BUDGET_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    let locked_dict = storage::new_dictionary(LOCKED_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
     
-    // Generate new workflow ID
-    let workflow_id = increment_workflow_count();
+    // The installing account becomes admin and holds the ADMIN role
+    storage::dictionary_put(roles_dict, &runtime::get_caller().to_string(), roles::ADMIN);
+    let admin = storage::new_uref(runtime::get_caller());
+    let pending_admin = storage::new_uref(None::<AccountHash>);
     
-    // Create workflow data
-    let workflow = WorkflowData {
-        id: workflow_id,
-        template_hash,
-        data_hash,
-        current_state: states::DRAFT,
-        creator: caller,
-        created_at: timestamp,
-        updated_at: timestamp,
-        is_completed: false,
+    // Create workflow and transition counters
+    let workflow_count = storage::new_uref(U256::zero());
+    let total_transitions = storage::new_uref(U256::zero());
+    
+    // Create audit stream counter
+    let audit_seq = storage::new_uref(0u64);
+    
+    // Create empty deadline index
+    let deadline_windows = storage::new_uref(Vec::<u64>::new());
+    let deadline_buckets_dict = storage::new_dictionary(DEADLINE_BUCKETS_DICT)
+        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    
+    // Record installation time
+    let deployed_at = storage::new_uref(get_block_time());
+    
+    // Create category-required flag (off by default)
+    let category_required = storage::new_uref(false);
+    
+    // Create intake-approval flag (off by default)
+    let intake_approval = storage::new_uref(false);
+    
+    // Create restricted-creation flag (open by default)
+    let restricted_creation = storage::new_uref(false);
+    
+    // Create comment length limit
+    let comment_max_len = storage::new_uref(DEFAULT_COMMENT_MAX_LEN);
+    
+    // Create history length and escalation limits
+    let max_history_len = storage::new_uref(DEFAULT_MAX_HISTORY_LEN);
+    let max_escalations = storage::new_uref(DEFAULT_MAX_ESCALATIONS);
+    
+    // Create emergency stop flag
+    let paused = storage::new_uref(false);
+    
+    // Create rate limit configuration (unlimited by default)
+    let rate_limit = storage::new_uref((0u32, 0u64));
+    
+    // Create global Merkle root (empty until the first sweep)
+    let global_root = storage::new_uref([0u8; 32]);
+    let global_tree_size = storage::new_uref(0u64);
+    
+    // Create contract version
+    let contract_version_uref = storage::new_uref(CONTRACT_VERSION);
+    
+    // Create events topic (None disables events)
+    let events_topic_uref = storage::new_uref(events_topic.clone());
+    
+    // Set up named keys
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(WORKFLOWS_DICT.into(), Key::from(workflows_dict));
+    named_keys.insert(TRANSITIONS_DICT.into(), Key::from(transitions_dict));
+    named_keys.insert(DATA_CHANGES_DICT.into(), Key::from(data_changes_dict));
+    named_keys.insert(COMPLIANCE_PROOFS_DICT.into(), Key::from(compliance_proofs_dict));
+    named_keys.insert(WORKFLOWS_ARCHIVE_DICT.into(), Key::from(workflows_archive_dict));
+    named_keys.insert(TRANSITIONS_ARCHIVE_DICT.into(), Key::from(transitions_archive_dict));
+    named_keys.insert(CREATOR_INDEX_DICT.into(), Key::from(creator_index_dict));
+    named_keys.insert(TEMPLATE_COUNTS_DICT.into(), Key::from(template_counts_dict));
+    named_keys.insert(COMMENTS_DICT.into(), Key::from(comments_dict));
+    named_keys.insert(METADATA_DICT.into(), Key::from(metadata_dict));
+    named_keys.insert(METADATA_CHANGES_DICT.into(), Key::from(metadata_changes_dict));
+    named_keys.insert(CREATOR_CHANGES_DICT.into(), Key::from(creator_changes_dict));
+    named_keys.insert(ORIGINAL_CREATORS_DICT.into(), Key::from(original_creators_dict));
+    named_keys.insert(HISTORY_PURGES_DICT.into(), Key::from(history_purges_dict));
+    named_keys.insert(PURGE_LOG_DICT.into(), Key::from(purge_log_dict));
+    named_keys.insert(ESCALATION_INDEX_DICT.into(), Key::from(escalation_index_dict));
+    named_keys.insert(COMMENT_MAX_LEN_KEY.into(), Key::from(comment_max_len));
+    named_keys.insert(MAX_HISTORY_LEN_KEY.into(), Key::from(max_history_len));
+    named_keys.insert(MAX_ESCALATIONS_KEY.into(), Key::from(max_escalations));
+    named_keys.insert(CATEGORIES_DICT.into(), Key::from(categories_dict));
+    named_keys.insert(ADMIN_KEY.into(), Key::from(admin));
+    named_keys.insert(PENDING_ADMIN_KEY.into(), Key::from(pending_admin));
+    named_keys.insert(ROLES_DICT.into(), Key::from(roles_dict));
+    named_keys.insert(DELEGATIONS_DICT.into(), Key::from(delegations_dict));
+    named_keys.insert(DELEGATES_DICT.into(), Key::from(delegates_dict));
+    named_keys.insert(TRANSITION_RULES_DICT.into(), Key::from(transition_rules_dict));
+    named_keys.insert(SELF_APPROVAL_DICT.into(), Key::from(self_approval_dict));
+    named_keys.insert(COMMENT_REQUIRED_DICT.into(), Key::from(comment_required_dict));
+    named_keys.insert(RULE_CONDITIONS_DICT.into(), Key::from(rule_conditions_dict));
+    named_keys.insert(TEMPLATE_APPROVERS_DICT.into(), Key::from(template_approvers_dict));
+    named_keys.insert(SEEN_REQUESTS_DICT.into(), Key::from(seen_requests_dict));
+    named_keys.insert(TIME_IN_STATE_DICT.into(), Key::from(time_in_state_dict));
+    named_keys.insert(STATE_SLAS_DICT.into(), Key::from(state_slas_dict));
+    named_keys.insert(TERMINAL_STATES_DICT.into(), Key::from(terminal_states_dict));
+    named_keys.insert(TERMINAL_GRACE_DICT.into(), Key::from(terminal_grace_dict));
+    named_keys.insert(
+        MIN_TRANSITION_INTERVALS_DICT.into(),
+        Key::from(min_transition_intervals_dict),
+    );
+    named_keys.insert(PENDING_APPROVALS_DICT.into(), Key::from(pending_approvals_dict));
+    named_keys.insert(STATE_CATALOG_DICT.into(), Key::from(state_catalog_dict));
+    named_keys.insert(STATE_COUNTS_DICT.into(), Key::from(state_counts_dict));
+    named_keys.insert(
+        TRANSITION_TYPE_COUNTS_DICT.into(),
+        Key::from(transition_type_counts_dict),
+    );
+    named_keys.insert(STATE_INDEX_DICT.into(), Key::from(state_index_dict));
+    named_keys.insert(GLOBAL_LEAVES_DICT.into(), Key::from(global_leaves_dict));
+    named_keys.insert(GLOBAL_NODES_DICT.into(), Key::from(global_nodes_dict));
+    named_keys.insert(AUDIT_STREAM_DICT.into(), Key::from(audit_stream_dict));
+    named_keys.insert(ROLE_CHANGES_DICT.into(), Key::from(role_changes_dict));
+    named_keys.insert(AUDIT_SEQ_KEY.into(), Key::from(audit_seq));
+    named_keys.insert(CALL_BUDGET_DICT.into(), Key::from(call_budget_dict));
+    named_keys.insert(LOCKED_DICT.into(), Key::from(locked_dict));
+    named_keys.insert(WORKFLOW_COUNT_KEY.into(), Key::from(workflow_count));
+    named_keys.insert(TOTAL_TRANSITIONS_KEY.into(), Key::from(total_transitions));
+    named_keys.insert(DEPLOYED_AT_KEY.into(), Key::from(deployed_at));
+    named_keys.insert(DEADLINE_WINDOWS_KEY.into(), Key::from(deadline_windows));
+    named_keys.insert(DEADLINE_BUCKETS_DICT.into(), Key::from(deadline_buckets_dict));
+    named_keys.insert(CATEGORY_REQUIRED_KEY.into(), Key::from(category_required));
+    named_keys.insert(INTAKE_APPROVAL_KEY.into(), Key::from(intake_approval));
+    named_keys.insert(RESTRICTED_CREATION_KEY.into(), Key::from(restricted_creation));
+    named_keys.insert(PAUSED_KEY.into(), Key::from(paused));
+    named_keys.insert(RATE_LIMIT_KEY.into(), Key::from(rate_limit));
+    named_keys.insert(GLOBAL_ROOT_KEY.into(), Key::from(global_root));
+    named_keys.insert(GLOBAL_TREE_SIZE_KEY.into(), Key::from(global_tree_size));
+    named_keys.insert(CONTRACT_VERSION_KEY.into(), Key::from(contract_version_uref));
+    named_keys.insert(EVENTS_TOPIC_KEY.into(), Key::from(events_topic_uref));
+    
+    // Define entry points; gated ones accept only members of MUTATION_GROUP
+    let mutation_access = if gate_mutations {
+        EntryPointAccess::Groups(vec![Group::new(MUTATION_GROUP)])
+    } else {
+        EntryPointAccess::Public
     };
+    let entry_points = build_entry_points(mutation_access);
     
-    // Store workflow
-    let workflows_dict = get_workflows_dict();
-    let key = workflow_id.to_string();
-    storage::dictionary_put(workflows_dict, &key, workflow);
+    // Register the events message topic, unless events are disabled
+    let mut message_topics = BTreeMap::new();
+    if let Some(topic) = events_topic {
+        message_topics.insert(topic, MessageTopicOperation::Add);
+    }
     
-    // Initialize empty transitions list
-    let transitions_dict = get_transitions_dict();
-    let empty_transitions: Vec<TransitionRecord> = Vec::new();
-    storage::dictionary_put(transitions_dict, &key, empty_transitions);
+    // Install contract - the package is created first so the access group
+    // exists before the version that references it
+    let (package_hash, access_uref) = storage::create_contract_package_at_hash();
+    runtime::put_key("workflow_contract_package", Key::Hash(package_hash.value()));
+    runtime::put_key("workflow_contract_access", access_uref.into());
+    named_keys.insert(PACKAGE_HASH_KEY.into(), Key::Hash(package_hash.value()));
+    if gate_mutations {
+        let member_uref = set_entry_group(package_hash, MUTATION_GROUP);
+        runtime::put_key(MUTATION_ACCESS_KEY, member_uref.into());
+    }
+    let (contract_hash, _contract_version) = storage::add_contract_version(
+        package_hash,
+        entry_points,
+        named_keys,
+        message_topics,
+    );
     
-    // Return the new workflow ID
-    runtime::ret(CLValue::from_t(workflow_id).unwrap_or_revert());
+    // Store contract hash for reference
+    runtime::put_key("workflow_contract", contract_hash.into());
 }
 
-/// Execute a state transition on a workflow.
-///
-/// # Arguments
-///
-/// * `workflow_id` - The workflow to transition
-/// * `to_state` - The target state
-/// * `actor_role` - The role mask of the caller
-/// * `comment_hash` - Hash of any comments/justification
-///
-/// # Errors
-///
-/// * `WorkflowNotFound` - Workflow does not exist
-/// * `InvalidTransition` - Transition not allowed
-/// * `WorkflowAlreadyCompleted` - Workflow in terminal state
-#[no_mangle]
-pub extern "C" fn transition_state() {
-    // Get arguments
-    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
-    let to_state: u8 = runtime::get_named_arg("to_state");
-    let actor_role: u64 = runtime::get_named_arg("actor_role");
-    let comment_hash: [u8; 32] = runtime::get_named_arg("comment_hash");
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
     
-    // Get caller and timestamp
-    let caller = runtime::get_caller();
-    let timestamp = get_block_time();
+    const RA: u64 = roles::REQUESTER;
+    const AP: u64 = roles::APPROVER;
+    const SA: u64 = roles::SENIOR_APPROVER;
     
-    // Load workflow
-    let workflows_dict = get_workflows_dict();
-    let key = workflow_id.to_string();
+    #[test]
+    fn assigned_role_authorizes_its_transitions() {
+        let required = required_role(states::PENDING_REVIEW, states::APPROVED).unwrap();
+        assert_eq!(check_role_mask(AP, required, None), Ok(()));
+        assert_eq!(check_role_mask(SA, required, None), Ok(()));
+        
+        let required = required_role(states::ESCALATED, states::APPROVED).unwrap();
+        assert_eq!(check_role_mask(SA, required, Some(SA)), Ok(()));
+        assert_eq!(check_role_mask(AP | SA, required, Some(AP)), Ok(()));
+    }
     
-    let mut workflow: WorkflowData = storage::dictionary_get(workflows_dict, &key)
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::WorkflowNotFound as u16));
+    #[test]
+    fn unauthorized_transitions_are_rejected() {
+        let denied = Err(WorkflowError::InsufficientPermissions);
+        
+        // No registered role at all
+        let required = required_role(states::DRAFT, states::PENDING_REVIEW).unwrap();
+        assert_eq!(check_role_mask(0, required, None), denied);
+        
+        // A requester cannot decide a review
+        let required = required_role(states::PENDING_REVIEW, states::APPROVED).unwrap();
+        assert_eq!(check_role_mask(RA, required, None), denied);
+        
+        // First-level approvers cannot settle an escalation
+        let required = required_role(states::ESCALATED, states::APPROVED).unwrap();
+        assert_eq!(check_role_mask(AP, required, None), denied);
+        
+        // Claiming a role the registry does not hold is rejected
+        let required = required_role(states::PENDING_REVIEW, states::APPROVED).unwrap();
+        assert_eq!(check_role_mask(AP, required, Some(AP | SA)), denied);
+    }
     
-    // Check workflow is not completed
-    if workflow.is_completed {
-        runtime::revert(ApiError::User(WorkflowError::WorkflowAlreadyCompleted as u16));
+    #[test]
+    fn undefined_transitions_have_no_role() {
+        assert_eq!(required_role(states::DRAFT, states::APPROVED), None);
+        assert_eq!(required_role(states::APPROVED, states::DRAFT), None);
+        assert_eq!(required_role(states::PENDING_REVIEW, states::DRAFT), None);
+        assert_eq!(mandatory_role(states::DRAFT, states::PENDING_REVIEW), None);
+        assert_eq!(mandatory_role(states::ESCALATED, states::APPROVED), Some(SA));
     }
     
-    // Validate transition
-    let from_state = workflow.current_state;
-    if !is_valid_transition(from_state, to_state) {
-        runtime::revert(ApiError::User(WorkflowError::InvalidTransition as u16));
+    #[test]
+    fn caller_ids_stay_near_the_count() {
+        let count = U256::from(7u64);
+        assert!(!is_countable_id(U256::zero(), count));
+        assert!(is_countable_id(U256::one(), count));
+        assert!(is_countable_id(U256::from(107u64), count));
+        assert!(!is_countable_id(U256::from(108u64), count));
+        assert!(!is_countable_id(U256::MAX, count));
+        assert!(is_countable_id(U256::MAX, U256::MAX));
+    }
+    
+    #[test]
+    fn derived_ids_are_deterministic() {
+        let template = [1u8; 32];
+        let data = [2u8; 32];
+        let creator = AccountHash::new([3u8; 32]);
+        let id = derive_workflow_id(&template, &data, creator, 7);
+        
+        // Same inputs, same ID; any changed input gives a different one
+        assert_eq!(id, derive_workflow_id(&template, &data, creator, 7));
+        assert_ne!(id, derive_workflow_id(&template, &data, creator, 8));
+        assert_ne!(id, derive_workflow_id(&[9u8; 32], &data, creator, 7));
+        assert_ne!(id, derive_workflow_id(&template, &[9u8; 32], creator, 7));
+        assert_ne!(id, derive_workflow_id(&template, &data, AccountHash::new([9u8; 32]), 7));
     }
     
-    // Note: Role-based permission validation is performed off-chain
-    // and the signed transaction proves the caller had authority.
-    // On-chain we record what role was claimed for audit purposes.
+    #[test]
+    fn derived_id_matches_documented_scheme() {
+        let template = [1u8; 32];
+        let data = [2u8; 32];
+        let creator = AccountHash::new([3u8; 32]);
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(&template);
+        preimage.extend_from_slice(&data);
+        preimage.extend_from_slice(&[3u8; 32]);
+        preimage.extend_from_slice(&7u64.to_le_bytes());
+        let digest = casper_types::Digest::hash(&preimage).value();
+        
+        let id = derive_workflow_id(&template, &data, creator, 7);
+        assert_eq!(id, U256::from_big_endian(&digest[..16]));
+        assert!(id < U256::one() << 128);
+    }
     
-    // Create transition record
-    let transition = TransitionRecord {
-        from_state,
-        to_state,
-        actor: caller,
-        actor_role,
-        timestamp,
-        comment_hash,
-    };
+    /// Non-admin entry points that change workflow records
+    const GATED_ENTRY_POINTS: &[&str] = &[
+        "create_workflow",
+        "create_workflow_with_id",
+        "create_workflows_batch",
+        "transition_state",
+        "transition_batch",
+        "cast_approval",
+        "escalate_to",
+        "auto_escalate",
+        "withdraw_to_draft",
+        "reopen_workflow",
+        "finalize",
+        "update_data_hash",
+        "set_metadata",
+        "store_comment",
+        "register_compliance_proof",
+        "archive_workflow",
+        "delegate_authority",
+    ];
     
-    // Update workflow state
-    workflow.current_state = to_state;
-    workflow.updated_at = timestamp;
-    workflow.is_completed = is_terminal_state(to_state);
+    #[test]
+    fn gated_install_restricts_every_mutation() {
+        let group = EntryPointAccess::Groups(vec![Group::new(MUTATION_GROUP)]);
+        let entry_points = build_entry_points(group.clone());
+        
+        for name in entry_points.keys() {
+            let access = entry_points.get(name).unwrap().access();
+            if GATED_ENTRY_POINTS.contains(&name.as_str()) {
+                assert_eq!(access, &group, "{} is not gated", name);
+            } else {
+                assert_eq!(access, &EntryPointAccess::Public, "{} is gated", name);
+            }
+        }
+        for name in GATED_ENTRY_POINTS {
+            assert!(entry_points.has_entry_point(name), "{} is missing", name);
+        }
+    }
     
-    // Store updated workflow
-    storage::dictionary_put(workflows_dict, &key, workflow);
+    #[test]
+    fn ungated_install_is_public() {
+        let entry_points = build_entry_points(EntryPointAccess::Public);
+        for name in entry_points.keys() {
+            let access = entry_points.get(name).unwrap().access();
+            assert_eq!(access, &EntryPointAccess::Public, "{} is gated", name);
+        }
+    }
     
-    // Append transition to history
-    let transitions_dict = get_transitions_dict();
-    let mut transitions: Vec<TransitionRecord> = storage::dictionary_get(transitions_dict, &key)
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
-        .unwrap_or_else(|| Vec::new());
+    #[test]
+    fn rule_conditions_gate_only_their_transition() {
+        let risk_check = [7u8; 32];
+        let conditions: Vec<RuleCondition> =
+            vec![(states::PENDING_REVIEW, states::APPROVED, risk_check)];
+        let (from, to) = (states::PENDING_REVIEW, states::APPROVED);
+        
+        assert_eq!(match_rule_condition(&conditions, from, to, Some(risk_check)), Ok(()));
+        assert_eq!(
+            match_rule_condition(&conditions, from, to, Some([8u8; 32])),
+            Err(WorkflowError::ConditionNotMet)
+        );
+        assert_eq!(
+            match_rule_condition(&conditions, from, to, None),
+            Err(WorkflowError::ConditionNotMet)
+        );
+        
+        // Other transitions of the template are not gated
+        let escalate = (states::PENDING_REVIEW, states::ESCALATED);
+        assert_eq!(match_rule_condition(&conditions, escalate.0, escalate.1, None), Ok(()));
+        assert_eq!(match_rule_condition(&[], from, to, None), Ok(()));
+    }
     
-    transitions.push(transition);
-    storage::dictionary_put(transitions_dict, &key, transitions);
-}
-
-/// Get the current state of a workflow.
-///
-/// # Arguments
-///
-/// * `workflow_id` - The workflow to query
-///
-/// # Returns
-///
-/// The WorkflowData struct
-#[no_mangle]
-pub extern "C" fn get_workflow_state() {
-    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    #[test]
+    fn mandatory_comments_apply_to_configured_states() {
+        let required = [states::REJECTED, states::ESCALATED];
+        let comment = [5u8; 32];
+        let missing = Err(WorkflowError::CommentRequired);
+        
+        assert_eq!(match_comment_required(&required, states::REJECTED, &[0u8; 32]), missing);
+        assert_eq!(match_comment_required(&required, states::ESCALATED, &[0u8; 32]), missing);
+        assert_eq!(match_comment_required(&required, states::REJECTED, &comment), Ok(()));
+        
+        // States without the requirement accept an empty comment
+        assert_eq!(match_comment_required(&required, states::APPROVED, &[0u8; 32]), Ok(()));
+        assert_eq!(match_comment_required(&[], states::REJECTED, &[0u8; 32]), Ok(()));
+    }
+    
+    #[test]
+    fn transitions_are_throttled_within_the_interval() {
+        let updated_at = 1_000_000;
+        
+        // Disabled by default
+        assert!(!is_too_soon(updated_at, updated_at, 0));
+        
+        // A 60 second interval blocks until a full minute of block time passed
+        assert!(is_too_soon(updated_at, updated_at, 60));
+        assert!(is_too_soon(updated_at, updated_at + 59_999, 60));
+        assert!(!is_too_soon(updated_at, updated_at + 60_000, 60));
+        
+        // A regressed block time never passes the throttle
+        assert!(is_too_soon(updated_at, updated_at - 1, 60));
+    }
+    
+    /// Reference fold over all leaves, as the root was computed before the
+    /// tree kept its internal nodes
+    fn merkle_root(mut level: Vec<[u8; 32]>) -> [u8; 32] {
+        if level.is_empty() {
+            return [0u8; 32];
+        }
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    _ => pair[0],
+                })
+                .collect();
+        }
+        level[0]
+    }
+    
+    /// An in-memory global tree swept like `update_global_root`
+    #[derive(Default)]
+    struct Tree {
+        size: u64,
+        nodes: BTreeMap<(u32, u64), [u8; 32]>,
+    }
+    
+    impl Tree {
+        fn sweep(&mut self, lo: u64, leaves: &[[u8; 32]]) -> [u8; 32] {
+            let hi = lo + leaves.len() as u64 - 1;
+            for (offset, leaf) in leaves.iter().enumerate() {
+                self.nodes.insert((0, lo + offset as u64), *leaf);
+            }
+            self.size = self.size.max(hi + 1);
+            let nodes = &self.nodes;
+            let read = |level, index| nodes.get(&(level, index)).copied().unwrap_or([0u8; 32]);
+            let (updated, root) = merkle_update(self.size, lo, hi, read);
+            for (level, index, node) in updated {
+                self.nodes.insert((level, index), node);
+            }
+            root
+        }
+        
+        fn leaves(&self) -> Vec<[u8; 32]> {
+            (0..self.size).map(|index| self.nodes[&(0, index)]).collect()
+        }
+    }
+    
+    fn leaf(seed: u64) -> [u8; 32] {
+        blake2b(seed.to_le_bytes())
+    }
+    
+    #[test]
+    fn incremental_root_matches_full_rebuild() {
+        let mut tree = Tree::default();
+        
+        // Grow the tree one sweep at a time, across odd and even sizes
+        for size in 1..=33u64 {
+            let root = tree.sweep(size - 1, &[leaf(size)]);
+            assert_eq!(root, merkle_root(tree.leaves()), "size {}", size);
+        }
+        
+        // Re-sweep ranges in the middle and at both edges
+        for (lo, len) in [(0u64, 3usize), (5, 1), (10, 7), (30, 3), (31, 2)] {
+            let leaves: Vec<[u8; 32]> = (0..len).map(|i| leaf(1000 + lo + i as u64)).collect();
+            let root = tree.sweep(lo, &leaves);
+            assert_eq!(root, merkle_root(tree.leaves()), "sweep at {}", lo);
+        }
+        
+        // A sweep extending the tree past its end
+        let leaves: Vec<[u8; 32]> = (0..10).map(|i| leaf(2000 + i)).collect();
+        let root = tree.sweep(28, &leaves);
+        assert_eq!(tree.size, 38);
+        assert_eq!(root, merkle_root(tree.leaves()));
+    }
+    
+    #[test]
+    fn root_changes_after_a_new_digest() {
+        let mut tree = Tree::default();
+        let before = tree.sweep(0, &[leaf(1), leaf(2), leaf(3)]);
+        
+        // A new workflow's digest joins the tree
+        let grown = tree.sweep(3, &[leaf(4)]);
+        assert_ne!(grown, before);
+        
+        // A refreshed digest after a transition changes the root again
+        let refreshed = tree.sweep(1, &[leaf(20)]);
+        assert_ne!(refreshed, grown);
+        
+        // A single leaf is its own root
+        let mut single = Tree::default();
+        assert_eq!(single.sweep(0, &[leaf(1)]), leaf(1));
+    }
+    
+    /// Bucket (deadline, workflow_id) entries by window like the index does
+    fn deadline_buckets(entries: &[(u64, u64)]) -> BTreeMap<u64, Vec<(u64, U256)>> {
+        let mut buckets: BTreeMap<u64, Vec<(u64, U256)>> = BTreeMap::new();
+        for (deadline, id) in entries {
+            let bucket = buckets.entry(deadline_window(*deadline)).or_default();
+            bucket.push((*deadline, U256::from(*id)));
+            bucket.sort();
+        }
+        buckets
+    }
+    
+    #[test]
+    fn only_breached_deadlines_are_returned() {
+        let day = DEADLINE_WINDOW_MS;
+        let buckets = deadline_buckets(&[
+            (day + 5, 1),
+            (day + 10, 2),
+            (3 * day, 3),
+            (3 * day + 1, 4),
+            (9 * day, 5),
+        ]);
+        let windows: Vec<u64> = buckets.keys().copied().collect();
+        let read = |window| buckets.get(&window).cloned().unwrap_or_default();
+        let ids = |ids: &[u64]| ids.iter().map(|id| U256::from(*id)).collect::<Vec<_>>();
+        
+        // Injected times before, within and across windows
+        assert_eq!(collect_past_deadline(&windows, read, day, 100), ids(&[]));
+        assert_eq!(collect_past_deadline(&windows, read, day + 6, 100), ids(&[1]));
+        assert_eq!(collect_past_deadline(&windows, read, 3 * day + 1, 100), ids(&[1, 2, 3]));
+        assert_eq!(collect_past_deadline(&windows, read, 10 * day, 100), ids(&[1, 2, 3, 4, 5]));
+        
+        // Most overdue first, capped at the limit
+        assert_eq!(collect_past_deadline(&windows, read, 10 * day, 3), ids(&[1, 2, 3]));
+        assert_eq!(collect_past_deadline(&windows, read, 10 * day, 0), ids(&[]));
+    }
+    
+    #[test]
+    fn comments_hash_with_the_recorded_algorithm() {
+        let keccak_empty = "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+        let keccak_abc = "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45";
+        let hex = |digest: Option<[u8; 32]>| digest.map(|digest| hash_key(&digest));
+        
+        assert_eq!(hex(comment_digest("", hash_algos::KECCAK256)).unwrap(), keccak_empty);
+        assert_eq!(hex(comment_digest("abc", hash_algos::KECCAK256)).unwrap(), keccak_abc);
+        assert_eq!(comment_digest("abc", hash_algos::BLAKE2B), Some(blake2b("abc")));
+        assert_eq!(comment_digest("abc", hash_algos::SHA256), None);
+    }
+    
+    fn record(seed: u8) -> TransitionRecord {
+        TransitionRecord {
+            from_state: states::DRAFT,
+            to_state: states::PENDING_REVIEW,
+            actor: AccountHash::new([seed; 32]),
+            actor_role: RA,
+            timestamp: 1_000 + u64::from(seed),
+            comment_hash: [seed; 32],
+            category_id: 0,
+            prev_hash: [0u8; 32],
+            external_ref: [0u8; 32],
+            confidential: false,
+            reason_code: 0,
+            hash_algo: hash_algos::KECCAK256,
+            block_height: 0,
+        }
+    }
+    
+    #[test]
+    fn confidential_hashes_are_only_shown_to_auditors() {
+        let mut secret = record(1);
+        secret.confidential = true;
+        let records = vec![secret, record(2)];
+        
+        let mut public = records.clone();
+        redact_confidential(&mut public, RA | AP);
+        assert_eq!(public[0].comment_hash, [0u8; 32]);
+        assert_eq!(public[1].comment_hash, [2u8; 32]);
+        
+        for privileged in [roles::AUDITOR, roles::ADMIN] {
+            let mut shown = records.clone();
+            redact_confidential(&mut shown, privileged);
+            assert_eq!(shown[0].comment_hash, [1u8; 32]);
+        }
+    }
+    
+    fn workflow(id: u64) -> WorkflowData {
+        WorkflowData {
+            id: U256::from(id),
+            template_hash: [1u8; 32],
+            data_hash: [2u8; 32],
+            current_state: states::PENDING_REVIEW,
+            creator: AccountHash::new([3u8; 32]),
+            created_at: 1_000,
+            updated_at: 2_000,
+            is_completed: false,
+            deadline: 9_000,
+            required_approvals: 2,
+            state_entered_at: 1_500,
+            assigned_approver: Some(AccountHash::new([4u8; 32])),
+            template_seq: 7,
+            is_voided: false,
+            hash_algo: hash_algos::BLAKE2B,
+            escalation_count: 1,
+            genesis_hash: [5u8; 32],
+            finalizes_at: 0,
+        }
+    }
+    
+    #[test]
+    fn workflow_data_round_trips() {
+        let bytes = workflow(42).to_bytes().unwrap();
+        assert_eq!(bytes[0], WORKFLOW_DATA_VERSION);
+        assert_eq!(bytes.len(), workflow(42).serialized_length());
+        
+        let (decoded, remainder) = WorkflowData::from_bytes(&bytes).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(decoded.to_bytes().unwrap(), bytes);
+    }
+    
+    /// Fields of `original` up to is_completed, the original unversioned layout.
+    fn unversioned_bytes(original: &WorkflowData) -> Vec<u8> {
+        let mut bytes = original.id.to_bytes().unwrap();
+        bytes.extend(original.template_hash.to_bytes().unwrap());
+        bytes.extend(original.data_hash.to_bytes().unwrap());
+        bytes.extend(original.current_state.to_bytes().unwrap());
+        bytes.extend(original.creator.to_bytes().unwrap());
+        bytes.extend(original.created_at.to_bytes().unwrap());
+        bytes.extend(original.updated_at.to_bytes().unwrap());
+        bytes.extend(original.is_completed.to_bytes().unwrap());
+        bytes
+    }
+    
+    #[test]
+    fn v2_workflow_data_decodes_with_defaults() {
+        let original = workflow(42);
+        let mut bytes = vec![2u8];
+        bytes.extend(unversioned_bytes(&original));
+        bytes.extend(original.deadline.to_bytes().unwrap());
+        bytes.extend(original.required_approvals.to_bytes().unwrap());
+        
+        let (decoded, remainder) = WorkflowData::from_bytes(&bytes).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(decoded.id, original.id);
+        assert_eq!(decoded.creator, original.creator);
+        assert_eq!((decoded.deadline, decoded.required_approvals), (9_000, 2));
+        assert_eq!(decoded.state_entered_at, decoded.updated_at);
+        assert_eq!(decoded.assigned_approver, None);
+        assert_eq!((decoded.template_seq, decoded.is_voided), (0, false));
+        assert_eq!(decoded.hash_algo, hash_algos::KECCAK256);
+        assert_eq!((decoded.escalation_count, decoded.finalizes_at), (0, 0));
+        assert_eq!(decoded.genesis_hash, [0u8; 32]);
+    }
+    
+    #[test]
+    fn unversioned_workflow_data_is_rejected() {
+        for id in [1, 42, 255, 256, 70_000] {
+            let bytes = unversioned_bytes(&workflow(id));
+            assert!(WorkflowData::from_bytes(&bytes).is_err(), "id {}", id);
+        }
+    }
+    
+    #[test]
+    fn unknown_workflow_data_versions_are_rejected() {
+        let mut bytes = workflow(42).to_bytes().unwrap();
+        for version in [0, 1, WORKFLOW_DATA_VERSION + 1] {
+            bytes[0] = version;
+            assert!(WorkflowData::from_bytes(&bytes).is_err());
+        }
+    }
+    
+    #[test]
+    fn hash_keys_are_lowercase_hex() {
+        let mut hash = [0u8; 32];
+        hash[0] = 0xab;
+        hash[31] = 0x0f;
+        let key = hash_key(&hash);
+        
+        assert_eq!(key.len(), 64);
+        assert!(key.starts_with("ab00"));
+        assert!(key.ends_with("000f"));
+        assert_eq!(hash_key(&[0xffu8; 32]), "ff".repeat(32));
+    }
+    
+    fn record_hash(transition: &TransitionRecord) -> [u8; 32] {
+        blake2b(transition.to_bytes().unwrap())
+    }
+    
+    fn chain(len: u8) -> Vec<TransitionRecord> {
+        let mut transitions: Vec<TransitionRecord> = Vec::new();
+        for seed in 1..=len {
+            let mut transition = record(seed);
+            transition.prev_hash = transitions.last().map(record_hash).unwrap_or([0u8; 32]);
+            transitions.push(transition);
+        }
+        transitions
+    }
+    
+    #[test]
+    fn intact_chain_verifies() {
+        assert!(chain_intact(&[], [0u8; 32], record_hash));
+        assert!(chain_intact(&chain(4), [0u8; 32], record_hash));
+    }
+    
+    #[test]
+    fn tampered_record_breaks_the_chain() {
+        let mut transitions = chain(4);
+        transitions[1].to_state = states::APPROVED;
+        assert!(!chain_intact(&transitions, [0u8; 32], record_hash));
+        
+        let mut transitions = chain(4);
+        transitions.remove(2);
+        assert!(!chain_intact(&transitions, [0u8; 32], record_hash));
+    }
+    
+    #[test]
+    fn purged_chain_links_to_its_anchor() {
+        let transitions = chain(4);
+        let anchor = record_hash(&transitions[1]);
+        assert!(chain_intact(&transitions[2..], anchor, record_hash));
+        assert!(!chain_intact(&transitions[2..], [0u8; 32], record_hash));
+    }
+    
+    fn v1_record_bytes(transition: &TransitionRecord) -> Vec<u8> {
+        let mut bytes = vec![1u8];
+        bytes.extend(transition.from_state.to_bytes().unwrap());
+        bytes.extend(transition.to_state.to_bytes().unwrap());
+        bytes.extend(transition.actor.to_bytes().unwrap());
+        bytes.extend(transition.actor_role.to_bytes().unwrap());
+        bytes.extend(transition.timestamp.to_bytes().unwrap());
+        bytes.extend(transition.comment_hash.to_bytes().unwrap());
+        bytes.extend(transition.category_id.to_bytes().unwrap());
+        bytes.extend(transition.prev_hash.to_bytes().unwrap());
+        bytes.extend(transition.external_ref.to_bytes().unwrap());
+        bytes.extend(transition.confidential.to_bytes().unwrap());
+        bytes
+    }
+    
+    #[test]
+    fn reason_codes_round_trip() {
+        for reason_code in [0, 3, u16::MAX] {
+            let mut transition = record(1);
+            transition.reason_code = reason_code;
+            let bytes = transition.to_bytes().unwrap();
+            assert_eq!(bytes.len(), transition.serialized_length());
+            
+            let (decoded, remainder) = TransitionRecord::from_bytes(&bytes).unwrap();
+            assert!(remainder.is_empty());
+            assert_eq!(decoded.reason_code, reason_code);
+            assert_eq!(decoded.to_bytes().unwrap(), bytes);
+        }
+    }
+    
+    #[test]
+    fn v1_records_decode_without_a_reason() {
+        let mut transition = record(1);
+        transition.reason_code = 9;
+        let bytes = v1_record_bytes(&transition);
+        
+        let (decoded, remainder) = TransitionRecord::from_bytes(&bytes).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(decoded.reason_code, 0);
+        assert_eq!(decoded.comment_hash, transition.comment_hash);
+    }
+    
+    #[test]
+    fn block_heights_round_trip() {
+        let mut transition = record(1);
+        transition.block_height = 1_234_567;
+        let bytes = transition.to_bytes().unwrap();
+        
+        let (decoded, _) = TransitionRecord::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.block_height, 1_234_567);
+        
+        // v3 records predate block heights
+        let mut v3 = v1_record_bytes(&transition);
+        v3[0] = 3;
+        v3.extend(transition.reason_code.to_bytes().unwrap());
+        v3.extend(transition.hash_algo.to_bytes().unwrap());
+        let (decoded, remainder) = TransitionRecord::from_bytes(&v3).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(decoded.block_height, 0);
+    }
+    
+    fn le(value: u64, len: usize) -> Vec<u8> {
+        value.to_le_bytes()[..len].to_vec()
+    }
+    
+    #[test]
+    fn workflow_data_bytes_are_pinned() {
+        let expected = [
+            vec![10],
+            vec![1, 42],
+            vec![1; 32],
+            vec![2; 32],
+            vec![states::PENDING_REVIEW],
+            vec![3; 32],
+            le(1_000, 8),
+            le(2_000, 8),
+            vec![0],
+            le(9_000, 8),
+            vec![2],
+            le(1_500, 8),
+            [vec![1], vec![4; 32]].concat(),
+            le(7, 8),
+            vec![0],
+            vec![hash_algos::BLAKE2B],
+            vec![1],
+            vec![5; 32],
+            le(0, 8),
+        ];
+        assert_eq!(expected.len(), WORKFLOW_DATA_LAYOUT.len() + 1);
+        assert_eq!(workflow(42).to_bytes().unwrap(), expected.concat());
+    }
+    
+    #[test]
+    fn transition_record_bytes_are_pinned() {
+        let expected = [
+            vec![4],
+            vec![states::DRAFT],
+            vec![states::PENDING_REVIEW],
+            vec![1; 32],
+            le(RA, 8),
+            le(1_001, 8),
+            vec![1; 32],
+            le(0, 4),
+            vec![0; 32],
+            vec![0; 32],
+            vec![0],
+            le(0, 2),
+            vec![hash_algos::KECCAK256],
+            le(0, 8),
+        ];
+        assert_eq!(expected.len(), TRANSITION_RECORD_LAYOUT.len() + 1);
+        assert_eq!(record(1).to_bytes().unwrap(), expected.concat());
+    }
+    
+    #[test]
+    fn data_change_record_bytes_are_pinned() {
+        let change = DataChangeRecord {
+            previous_hash: [1u8; 32],
+            data_hash: [2u8; 32],
+            hash_algo: hash_algos::SHA256,
+            actor: AccountHash::new([3u8; 32]),
+            timestamp: 5_000,
+        };
+        let expected = [
+            vec![1],
+            vec![1; 32],
+            vec![2; 32],
+            vec![hash_algos::SHA256],
+            vec![3; 32],
+            le(5_000, 8),
+        ];
+        assert_eq!(expected.len(), DATA_CHANGE_RECORD_LAYOUT.len() + 1);
+        let expected = expected.concat();
+        assert_eq!(change.to_bytes().unwrap(), expected);
+        
+        let (decoded, remainder) = DataChangeRecord::from_bytes(&expected).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(decoded.timestamp, 5_000);
+        assert_eq!(decoded.actor, change.actor);
+    }
+    
+    #[test]
+    fn audit_stream_follows_sequence_order() {
+        // A role grant (seq 1) followed by a transition of workflow 7 (seq 2)
+        let stream: BTreeMap<u64, (u8, U256)> = [
+            (1, (audit_kinds::ROLE_CHANGE, U256::from(1))),
+            (2, (audit_kinds::TRANSITION, U256::from(7))),
+            (3, (audit_kinds::ROLE_CHANGE, U256::from(3))),
+        ]
+        .into_iter()
+        .collect();
+        let read = |seq: u64| stream.get(&seq).copied();
+        
+        let all = collect_audit_entries(0, 3, MAX_PAGE_SIZE, read);
+        assert_eq!(all, stream.values().copied().collect::<Vec<_>>());
+        assert_eq!(all[0].0, audit_kinds::ROLE_CHANGE);
+        assert_eq!(all[1], (audit_kinds::TRANSITION, U256::from(7)));
+        
+        assert_eq!(collect_audit_entries(0, 3, 1, read), all[..1]);
+        assert_eq!(collect_audit_entries(1, 3, 1, read), all[1..2]);
+        assert_eq!(collect_audit_entries(0, 2, MAX_PAGE_SIZE, read), all[..2]);
+        assert!(collect_audit_entries(3, 3, MAX_PAGE_SIZE, read).is_empty());
+    }
+    
+    fn committed_workflow() -> WorkflowData {
+        let mut workflow = workflow(42);
+        workflow.genesis_hash = genesis_hash(
+            workflow.id,
+            &workflow.template_hash,
+            workflow.creator,
+            workflow.created_at,
+        );
+        workflow
+    }
+    
+    #[test]
+    fn genesis_hash_matches_documented_scheme() {
+        let workflow = committed_workflow();
+        let mut preimage = vec![0u8; 31];
+        preimage.push(42);
+        preimage.extend_from_slice(&workflow.template_hash);
+        preimage.extend_from_slice(workflow.creator.as_bytes());
+        preimage.extend_from_slice(&workflow.created_at.to_le_bytes());
+        
+        assert_eq!(workflow.genesis_hash, casper_types::Digest::hash(&preimage).value());
+    }
+    
+    #[test]
+    fn integrity_holds_across_transitions() {
+        let mut workflow = committed_workflow();
+        let creator = workflow.creator;
+        assert!(integrity_holds(&workflow, creator));
+        
+        workflow.current_state = states::APPROVED;
+        workflow.updated_at = 5_000;
+        workflow.state_entered_at = 5_000;
+        workflow.data_hash = [9u8; 32];
+        workflow.is_completed = true;
+        workflow.escalation_count = 2;
+        assert!(integrity_holds(&workflow, creator));
+        
+        // Reassignment is checked against the original creator
+        workflow.creator = AccountHash::new([8u8; 32]);
+        assert!(integrity_holds(&workflow, creator));
+        assert!(!integrity_holds(&workflow, workflow.creator));
+    }
+    
+    #[test]
+    fn altered_creation_fields_fail_integrity() {
+        let creator = committed_workflow().creator;
+        
+        let mut workflow = committed_workflow();
+        workflow.created_at += 1;
+        assert!(!integrity_holds(&workflow, creator));
+        
+        let mut workflow = committed_workflow();
+        workflow.template_hash = [7u8; 32];
+        assert!(!integrity_holds(&workflow, creator));
+        
+        // Records without a commitment never verify
+        let mut workflow = committed_workflow();
+        workflow.genesis_hash = [0u8; 32];
+        assert!(!integrity_holds(&workflow, creator));
+    }
+    
+    #[test]
+    fn purge_removes_only_older_records() {
+        // record(seed) is timestamped 1_000 + seed
+        let transitions = chain(4);
+        assert_eq!(purge_count(&transitions, 1_000), 0);
+        assert_eq!(purge_count(&transitions, 1_002), 1);
+        assert_eq!(purge_count(&transitions, 1_004), 3);
+        assert_eq!(purge_count(&[], 5_000), 0);
+        
+        // An older record after a newer one stays, keeping the history contiguous
+        let mut out_of_order = chain(4);
+        out_of_order[2].timestamp = 900;
+        assert_eq!(purge_count(&out_of_order, 1_002), 1);
+    }
+    
+    #[test]
+    fn purge_never_removes_the_latest_record() {
+        let transitions = chain(4);
+        assert_eq!(purge_count(&transitions, u64::MAX), 3);
+        assert_eq!(purge_count(&transitions[..1], u64::MAX), 0);
+        
+        let count = purge_count(&transitions, u64::MAX);
+        let anchor = record_hash(&transitions[count - 1]);
+        assert!(chain_intact(&transitions[count..], anchor, record_hash));
+    }
+    
+    #[test]
+    fn counter_skips_claimed_ids() {
+        let claimed = [U256::from(4), U256::from(5)];
+        let lookups = core::cell::Cell::new(0);
+        let in_use = |id: U256| {
+            lookups.set(lookups.get() + 1);
+            claimed.contains(&id)
+        };
+        
+        assert_eq!(next_free_id(U256::zero(), in_use), Some(U256::one()));
+        assert_eq!(lookups.get(), 1);
+        
+        // One lookup per candidate, stopping at the first free ID
+        lookups.set(0);
+        assert_eq!(next_free_id(U256::from(3), in_use), Some(U256::from(6)));
+        assert_eq!(lookups.get(), 3);
+    }
+    
+    #[test]
+    fn counter_overflow_is_reported() {
+        assert_eq!(next_free_id(U256::MAX, |_| false), None);
+        assert_eq!(next_free_id(U256::MAX - 1, |id| id != U256::MAX), Some(U256::MAX));
+        assert_eq!(next_free_id(U256::MAX - 1, |_| true), None);
+    }
+    
+    #[test]
+    fn rejected_workflows_can_be_appealed_then_approved() {
+        let denied = Err(WorkflowError::InsufficientPermissions);
+        
+        assert!(is_appeal_transition(states::REJECTED, states::APPEALED));
+        assert!(!is_appeal_transition(states::REJECTED, states::DRAFT));
+        let appeal = required_role(states::REJECTED, states::APPEALED).unwrap();
+        assert_eq!(check_role_mask(RA, appeal, None), Ok(()));
+        assert_eq!(check_role_mask(AP, appeal, None), denied);
+        
+        let decision = required_role(states::APPEALED, states::APPROVED).unwrap();
+        assert_eq!(check_role_mask(SA, decision, None), Ok(()));
+        assert_eq!(check_role_mask(AP, decision, None), denied);
+        assert!(required_role(states::APPEALED, states::REJECTED).is_some());
+        assert_eq!(required_role(states::APPEALED, states::DRAFT), None);
+    }
+    
+    #[test]
+    fn active_delegations_grant_their_roles() {
+        let delegate = AccountHash::new([2u8; 32]);
+        let delegation = (delegate, 5_000, AP);
+        
+        assert_eq!(delegated_mask(delegation, delegate, AP | RA, 4_999), AP);
+        let required = required_role(states::PENDING_REVIEW, states::APPROVED).unwrap();
+        let mask = delegated_mask(delegation, delegate, AP, 4_999);
+        assert_eq!(check_role_mask(mask, required, None), Ok(()));
+        
+        // Only the named delegate benefits
+        assert_eq!(delegated_mask(delegation, AccountHash::new([3u8; 32]), AP, 0), 0);
+    }
+    
+    #[test]
+    fn expired_delegations_are_absent() {
+        let delegate = AccountHash::new([2u8; 32]);
+        let delegation = (delegate, 5_000, AP);
+        assert_eq!(delegated_mask(delegation, delegate, AP, 5_000), 0);
+        assert_eq!(delegated_mask(delegation, delegate, AP, 9_000), 0);
+        assert_eq!(
+            check_delegation(AccountHash::new([1u8; 32]), AP, delegate, AP, 5_000, 5_000),
+            Err(WorkflowError::InvalidArgument)
+        );
+    }
+    
+    #[test]
+    fn over_broad_delegations_are_refused() {
+        let delegator = AccountHash::new([1u8; 32]);
+        let delegate = AccountHash::new([2u8; 32]);
+        let denied = Err(WorkflowError::InsufficientPermissions);
+        
+        assert_eq!(check_delegation(delegator, AP, delegate, AP, 5_000, 0), Ok(()));
+        assert_eq!(check_delegation(delegator, AP, delegate, AP | SA, 5_000, 0), denied);
+        assert_eq!(
+            check_delegation(delegator, AP, delegator, AP, 5_000, 0),
+            Err(WorkflowError::InvalidArgument)
+        );
+        
+        // Roles the delegator has since lost are not passed on
+        assert_eq!(delegated_mask((delegate, 5_000, AP | SA), delegate, AP, 0), AP);
+    }
+    
+    fn step(from_state: u8, to_state: u8) -> TransitionRecord {
+        let mut transition = record(1);
+        transition.from_state = from_state;
+        transition.to_state = to_state;
+        transition
+    }
+    
+    #[test]
+    fn state_path_lists_each_visited_state() {
+        let transitions = [
+            step(states::DRAFT, states::PENDING_REVIEW),
+            step(states::PENDING_REVIEW, states::ESCALATED),
+            step(states::ESCALATED, states::APPROVED),
+        ];
+        assert_eq!(
+            state_path(&transitions, states::APPROVED),
+            vec![states::DRAFT, states::PENDING_REVIEW, states::ESCALATED, states::APPROVED]
+        );
+        
+        // Consecutive repeats of a state collapse
+        let transitions = [
+            step(states::DRAFT, states::PENDING_REVIEW),
+            step(states::PENDING_REVIEW, states::PENDING_REVIEW),
+        ];
+        assert_eq!(
+            state_path(&transitions, states::PENDING_REVIEW),
+            vec![states::DRAFT, states::PENDING_REVIEW]
+        );
+        assert_eq!(state_path(&[], states::DRAFT), vec![states::DRAFT]);
+    }
+    
+    #[test]
+    fn only_decisions_are_final_transitions() {
+        assert!(is_builtin_terminal_state(states::APPROVED));
+        assert!(is_builtin_terminal_state(states::REJECTED));
+        assert!(is_builtin_terminal_state(states::CANCELLED));
+        assert!(!is_builtin_terminal_state(states::ESCALATED));
+        assert!(!is_builtin_terminal_state(states::PENDING_REVIEW));
+        assert!(!is_builtin_terminal_state(states::APPEALED));
+    }
+    
+    #[test]
+    fn templates_can_make_rejection_non_terminal() {
+        let reworkable = [states::APPROVED, states::CANCELLED];
+        assert_eq!(configured_terminal(Some(&reworkable), states::REJECTED), Some(false));
+        assert_eq!(configured_terminal(Some(&reworkable), states::APPROVED), Some(true));
+        
+        // Without a configuration the built-in set applies
+        assert_eq!(configured_terminal(None, states::REJECTED), None);
+        assert_eq!(configured_terminal(Some(&[]), states::REJECTED), None);
+    }
+    
+    #[test]
+    fn average_time_in_state_over_known_durations() {
+        // Two workflows leave PENDING_REVIEW after 30s and 90s
+        let aggregate = add_time_sample((0, 0), 30_000).unwrap();
+        let aggregate = add_time_sample(aggregate, 90_000).unwrap();
+        assert_eq!(aggregate, (120_000, 2));
+        assert_eq!(average_time(aggregate), 60_000);
+        
+        assert_eq!(average_time((0, 0)), 0);
+        assert_eq!(add_time_sample((u64::MAX, 1), 1), None);
+    }
     
-    let workflows_dict = get_workflows_dict();
-    let key = workflow_id.to_string();
+    #[test]
+    fn workflows_become_overdue_after_their_sla() {
+        // A one-minute SLA entered at blocktime 1_000
+        let mut workflow = workflow(42);
+        workflow.deadline = checked_sla_deadline(1_000, 60).unwrap();
+        assert_eq!(workflow.deadline, 61_000);
+        
+        assert!(!is_overdue(&workflow, 1_000));
+        assert!(!is_overdue(&workflow, 61_000));
+        assert!(is_overdue(&workflow, 61_001));
+        
+        workflow.is_completed = true;
+        assert!(!is_overdue(&workflow, 61_001));
+    }
     
-    let workflow: WorkflowData = storage::dictionary_get(workflows_dict, &key)
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::WorkflowNotFound as u16));
+    #[test]
+    fn workflows_without_an_sla_are_never_overdue() {
+        let mut workflow = workflow(42);
+        workflow.deadline = checked_sla_deadline(1_000, 0).unwrap();
+        assert_eq!(workflow.deadline, 0);
+        assert!(!is_overdue(&workflow, u64::MAX));
+        
+        // Voided workflows are out of operation
+        workflow.deadline = 5_000;
+        workflow.is_voided = true;
+        assert!(!is_overdue(&workflow, 9_000));
+        
+        assert_eq!(checked_sla_deadline(u64::MAX, 1), None);
+    }
     
-    runtime::ret(CLValue::from_t(workflow).unwrap_or_revert());
-}
-
-/// Get the transition history of a workflow.
-///
-/// # Arguments
-///
-/// * `workflow_id` - The workflow to query
-///
-/// # Returns
-///
-/// Vector of TransitionRecord
-#[no_mangle]
-pub extern "C" fn get_workflow_history() {
-    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    /// `check_caller_duties` with fixed template settings: an open approver
+    /// pool, self-approval forbidden and the default escalation limit.
+    fn duties(
+        workflow: &WorkflowData,
+        to_state: u8,
+        caller: AccountHash,
+    ) -> Result<(), WorkflowError> {
+        let max_escalations = || DEFAULT_MAX_ESCALATIONS;
+        check_caller_duties(workflow, to_state, caller, || true, || false, max_escalations)
+    }
     
-    let transitions_dict = get_transitions_dict();
-    let key = workflow_id.to_string();
+    #[test]
+    fn creators_cannot_approve_their_own_intake() {
+        let mut workflow = workflow(42);
+        workflow.current_state = states::CREATED_PENDING;
+        let reviewer = AccountHash::new([9u8; 32]);
+        
+        assert_eq!(
+            duties(&workflow, states::DRAFT, workflow.creator),
+            Err(WorkflowError::InsufficientPermissions)
+        );
+        assert_eq!(duties(&workflow, states::DRAFT, reviewer), Ok(()));
+        
+        let intake = required_role(states::CREATED_PENDING, states::DRAFT).unwrap();
+        assert_eq!(check_role_mask(roles::REVIEWER, intake, None), Ok(()));
+        assert!(check_role_mask(RA, intake, None).is_err());
+    }
     
-    let transitions: Vec<TransitionRecord> = storage::dictionary_get(transitions_dict, &key)
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
-        .unwrap_or_else(|| Vec::new());
+    #[test]
+    fn self_approval_is_forbidden_unless_opted_out() {
+        let workflow = workflow(42);
+        let creator = workflow.creator;
+        let other = AccountHash::new([9u8; 32]);
+        let max_escalations = || DEFAULT_MAX_ESCALATIONS;
+        
+        // Enforced
+        assert_eq!(
+            duties(&workflow, states::APPROVED, creator),
+            Err(WorkflowError::SelfApprovalForbidden)
+        );
+        assert_eq!(duties(&workflow, states::APPROVED, other), Ok(()));
+        assert_eq!(duties(&workflow, states::REJECTED, creator), Ok(()));
+        
+        // Opted out
+        let result = check_caller_duties(
+            &workflow,
+            states::APPROVED,
+            creator,
+            || true,
+            || true,
+            max_escalations,
+        );
+        assert_eq!(result, Ok(()));
+    }
     
-    runtime::ret(CLValue::from_t(transitions).unwrap_or_revert());
-}
-
-/// Get the total number of workflows created.
-///
-/// # Returns
-///
-/// U256 count
-#[no_mangle]
-pub extern "C" fn get_workflow_count() {
-    let count = read_workflow_count();
-    runtime::ret(CLValue::from_t(count).unwrap_or_revert());
-}
-
-/// Register a compliance proof for an approved workflow.
-/// 
-/// This entry point stores a cryptographic hash of the compliance proof JSON
-/// on-chain, providing immutable evidence that the workflow was approved
-/// with specific documents reviewed.
-///
-/// # Arguments
-///
-/// * `workflow_id` - The workflow ID (U256) to register proof for
-/// * `proof_hash` - SHA-256 hash of the compliance proof JSON (32 bytes)
-///
-/// # Errors
-///
-/// * `WorkflowNotFound` - Workflow does not exist
-/// * `WorkflowNotApproved` - Workflow is not in APPROVED state
-/// * `ComplianceProofAlreadyExists` - Proof already registered for this workflow
-#[no_mangle]
-pub extern "C" fn register_compliance_proof() {
-    // Get arguments
-    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
-    let proof_hash: [u8; 32] = runtime::get_named_arg("proof_hash");
+    #[test]
+    fn escalations_stop_at_the_limit() {
+        let mut workflow = workflow(42);
+        let approver = AccountHash::new([9u8; 32]);
+        
+        workflow.escalation_count = 0;
+        assert_eq!(duties(&workflow, states::ESCALATED, approver), Ok(()));
+        
+        workflow.escalation_count = DEFAULT_MAX_ESCALATIONS;
+        assert_eq!(
+            duties(&workflow, states::ESCALATED, approver),
+            Err(WorkflowError::MaxEscalationReached)
+        );
+        
+        // A raised limit admits another escalation
+        let result =
+            check_caller_duties(&workflow, states::ESCALATED, approver, || true, || false, || 2);
+        assert_eq!(result, Ok(()));
+    }
     
-    // Load workflow to verify it exists and is approved
-    let workflows_dict = get_workflows_dict();
-    let key = workflow_id.to_string();
+    /// `duties` with the approver pool read through `in_pool` and escalation
+    /// headroom left.
+    fn duties_in<P: Fn() -> bool>(
+        workflow: &WorkflowData,
+        to_state: u8,
+        caller: AccountHash,
+        in_pool: P,
+    ) -> Result<(), WorkflowError> {
+        let max_escalations = || workflow.escalation_count + 1;
+        check_caller_duties(workflow, to_state, caller, in_pool, || false, max_escalations)
+    }
     
-    let workflow: WorkflowData = storage::dictionary_get(workflows_dict, &key)
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::WorkflowNotFound as u16));
+    #[test]
+    fn review_decisions_require_a_pool_approver() {
+        let listed = AccountHash::new([7u8; 32]);
+        let unlisted = AccountHash::new([8u8; 32]);
+        let pool = [listed];
+        assert!(in_approver_pool(&pool, listed));
+        assert!(!in_approver_pool(&pool, unlisted));
+        assert!(in_approver_pool(&[], unlisted));
+        
+        let workflow = workflow(42);
+        for caller in [listed, unlisted] {
+            let in_pool = || in_approver_pool(&pool, caller);
+            let expected = if caller == listed {
+                Ok(())
+            } else {
+                Err(WorkflowError::InsufficientPermissions)
+            };
+            for to_state in [states::APPROVED, states::REJECTED] {
+                assert_eq!(duties_in(&workflow, to_state, caller, in_pool), expected);
+            }
+        }
+        
+        // Escalating is not a decision and ignores the pool
+        let in_pool = || in_approver_pool(&pool, unlisted);
+        assert_eq!(duties_in(&workflow, states::ESCALATED, unlisted, in_pool), Ok(()));
+    }
     
-    // Verify workflow is in APPROVED state
-    if workflow.current_state != states::APPROVED {
-        runtime::revert(ApiError::User(WorkflowError::WorkflowNotApproved as u16));
+    #[test]
+    fn call_budget_resets_in_the_next_window() {
+        // Two calls per 60s window
+        let (budget, window_seconds) = (2, 60);
+        let mut used: BTreeMap<u64, u32> = BTreeMap::new();
+        let mut call = |now: u64| {
+            let slot = used.entry(budget_window(now, window_seconds)).or_insert(0);
+            spend_call(*slot, budget).map(|spent| *slot = spent).is_some()
+        };
+        
+        assert!(call(0));
+        assert!(call(30_000));
+        assert!(!call(59_999));
+        assert!(call(60_000));
+        assert!(call(119_999));
+        assert!(!call(119_999));
     }
     
-    // Check if proof already exists for this workflow
-    let proofs_dict = get_compliance_proofs_dict();
-    let existing: Option<[u8; 32]> = storage::dictionary_get(proofs_dict, &key)
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    #[test]
+    fn histories_stop_at_the_length_limit() {
+        let mut transitions = chain(3);
+        let max_len = 4;
+        assert!(has_history_room(transitions.len(), max_len));
+        transitions.push(record(4));
+        assert!(!has_history_room(transitions.len(), max_len));
+        
+        assert!(has_history_room(0, DEFAULT_MAX_HISTORY_LEN));
+        assert!(!has_history_room(DEFAULT_MAX_HISTORY_LEN as usize, DEFAULT_MAX_HISTORY_LEN));
+        assert!(!has_history_room(0, 0));
+    }
     
-    if existing.is_some() {
-        runtime::revert(ApiError::User(WorkflowError::ComplianceProofAlreadyExists as u16));
+    #[test]
+    fn regressed_blocktime_is_rejected() {
+        // workflow(42) was last updated at 2_000
+        let workflow = workflow(42);
+        assert_eq!(check_monotonic(&workflow, 1_999), Err(WorkflowError::TimestampRegression));
+        assert_eq!(check_monotonic(&workflow, 2_000), Ok(()));
+        assert_eq!(check_monotonic(&workflow, 2_001), Ok(()));
     }
     
-    // Store the compliance proof hash (immutable - can only be set once)
-    storage::dictionary_put(proofs_dict, &key, proof_hash);
-}
-
-/// Get the compliance proof hash for a workflow.
-///
-/// # Arguments
-///
-/// * `workflow_id` - The workflow to query
-///
-/// # Returns
-///
-/// The 32-byte proof hash, or reverts if not found
-#[no_mangle]
-pub extern "C" fn get_compliance_proof() {
-    let workflow_id: U256 = runtime::get_named_arg("workflow_id");
+    #[test]
+    fn unknown_hash_algorithms_are_rejected() {
+        for hash_algo in [hash_algos::KECCAK256, hash_algos::BLAKE2B, hash_algos::SHA256] {
+            assert_eq!(check_hash_algo(hash_algo), Ok(()));
+        }
+        assert_eq!(check_hash_algo(3), Err(WorkflowError::InvalidArgument));
+        assert_eq!(check_hash_algo(u8::MAX), Err(WorkflowError::InvalidArgument));
+    }
     
-    let proofs_dict = get_compliance_proofs_dict();
-    let key = workflow_id.to_string();
+    #[test]
+    fn hash_algorithms_round_trip() {
+        let mut transition = record(1);
+        transition.hash_algo = hash_algos::SHA256;
+        let (decoded, _) = TransitionRecord::from_bytes(&transition.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.hash_algo, hash_algos::SHA256);
+        
+        let (decoded, _) = WorkflowData::from_bytes(&workflow(42).to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.hash_algo, hash_algos::BLAKE2B);
+        
+        // Records written before hash_algo existed were keccak256
+        let (decoded, _) = TransitionRecord::from_bytes(&v1_record_bytes(&transition)).unwrap();
+        assert_eq!(decoded.hash_algo, hash_algos::KECCAK256);
+    }
     
-    let proof_hash: [u8; 32] = storage::dictionary_get(proofs_dict, &key)
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16))
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::WorkflowNotFound as u16));
+    #[test]
+    fn workflows_finalize_only_after_the_grace_period() {
+        // Approved at blocktime 10_000 under a one-hour grace period
+        let mut workflow = workflow(42);
+        workflow.current_state = states::APPROVED;
+        workflow.finalizes_at = checked_sla_deadline(10_000, 3_600).unwrap();
+        
+        for now in [10_000, workflow.finalizes_at - 1] {
+            assert!(!is_grace_over(&workflow, now));
+            assert_eq!(check_finalize(&workflow, now), Err(WorkflowError::GracePeriodActive));
+        }
+        assert!(is_grace_over(&workflow, workflow.finalizes_at));
+        assert_eq!(check_finalize(&workflow, workflow.finalizes_at), Ok(()));
+        
+        workflow.is_completed = true;
+        assert_eq!(check_finalize(&workflow, u64::MAX), Err(WorkflowError::InvalidTransition));
+    }
     
-    runtime::ret(CLValue::from_t(proof_hash).unwrap_or_revert());
-}
-
-// =============================================================================
-// Contract Installation
-// =============================================================================
-
-/// Contract entry point for installation.
-/// Sets up named keys and entry points.
-#[no_mangle]
-pub extern "C" fn call() {
-    // Create dictionaries for storage
-    let workflows_dict = storage::new_dictionary(WORKFLOWS_DICT)
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
-    let transitions_dict = storage::new_dictionary(TRANSITIONS_DICT)
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
-    let compliance_proofs_dict = storage::new_dictionary(COMPLIANCE_PROOFS_DICT)
-        .unwrap_or_revert_with(ApiError::User(WorkflowError::StorageError as u16));
+    #[test]
+    fn workflows_without_a_grace_period_have_nothing_to_finalize() {
+        let workflow = workflow(42);
+        assert!(!is_grace_over(&workflow, u64::MAX));
+        assert_eq!(check_finalize(&workflow, u64::MAX), Err(WorkflowError::InvalidTransition));
+    }
     
-    // Create workflow counter
-    let workflow_count = storage::new_uref(U256::zero());
+    #[test]
+    fn workflows_import_into_open_and_terminal_states() {
+        let importable = [
+            states::DRAFT,
+            states::PENDING_REVIEW,
+            states::APPEALED,
+            states::APPROVED,
+            states::REJECTED,
+            states::CANCELLED,
+        ];
+        for state in importable {
+            assert_eq!(check_import_state(state, 1, is_builtin_state), Ok(()));
+        }
+        
+        // Terminal imports are completed on arrival
+        let completed: Vec<u8> =
+            importable.into_iter().filter(|state| is_builtin_terminal_state(*state)).collect();
+        assert_eq!(completed, vec![states::APPROVED, states::REJECTED, states::CANCELLED]);
+    }
     
-    // Create contract version
-    let contract_version_uref = storage::new_uref(CONTRACT_VERSION);
+    #[test]
+    fn imports_into_escalated_or_unknown_states_are_rejected() {
+        let invalid = Err(WorkflowError::InvalidArgument);
+        assert_eq!(check_import_state(states::ESCALATED, 1, is_builtin_state), invalid);
+        assert_eq!(check_import_state(states::CUSTOM_BASE, 1, is_builtin_state), invalid);
+        assert_eq!(check_import_state(states::DRAFT, 0, is_builtin_state), invalid);
+        
+        // Registered custom states are accepted
+        let catalog = |state: u8| state == states::CUSTOM_BASE || is_builtin_state(state);
+        assert_eq!(check_import_state(states::CUSTOM_BASE, 1, catalog), Ok(()));
+    }
     
-    // Set up named keys
-    let mut named_keys = NamedKeys::new();
-    named_keys.insert(WORKFLOWS_DICT.into(), Key::from(workflows_dict));
-    named_keys.insert(TRANSITIONS_DICT.into(), Key::from(transitions_dict));
-    named_keys.insert(COMPLIANCE_PROOFS_DICT.into(), Key::from(compliance_proofs_dict));
-    named_keys.insert(WORKFLOW_COUNT_KEY.into(), Key::from(workflow_count));
-    named_keys.insert(CONTRACT_VERSION_KEY.into(), Key::from(contract_version_uref));
+    #[test]
+    fn only_the_proposed_successor_accepts_the_handoff() {
+        let successor = AccountHash::new([6u8; 32]);
+        let stranger = AccountHash::new([7u8; 32]);
+        let denied = Err(WorkflowError::InsufficientPermissions);
+        
+        // Nothing proposed yet
+        assert_eq!(check_admin_acceptance(None, successor), denied);
+        
+        // After propose_admin(successor)
+        assert_eq!(check_admin_acceptance(Some(successor), stranger), denied);
+        assert_eq!(check_admin_acceptance(Some(successor), successor), Ok(()));
+    }
     
-    // Define entry points
-    let mut entry_points = EntryPoints::new();
+    #[test]
+    fn open_creation_accepts_anyone() {
+        assert_eq!(check_may_create(false, || 0), Ok(()));
+        assert_eq!(check_may_create(false, || AP), Ok(()));
+    }
     
-    // create_workflow - Casper 2.0 uses EntryPointType::Called
-    entry_points.add_entry_point(EntryPoint::new(
-        "create_workflow",
-        vec![
-            Parameter::new("template_hash", CLType::ByteArray(32)),
-            Parameter::new("data_hash", CLType::ByteArray(32)),
-        ],
-        CLType::U256,
-        EntryPointAccess::Public,
-        EntryPointType::Called,
-    ).into());
+    #[test]
+    fn restricted_creation_requires_a_requester() {
+        assert_eq!(check_may_create(true, || RA), Ok(()));
+        assert_eq!(check_may_create(true, || RA | AP), Ok(()));
+        assert_eq!(check_may_create(true, || 0), Err(WorkflowError::InsufficientPermissions));
+        assert_eq!(check_may_create(true, || AP | SA), Err(WorkflowError::InsufficientPermissions));
+    }
     
-    // transition_state
-    entry_points.add_entry_point(EntryPoint::new(
-        "transition_state",
-        vec![
-            Parameter::new("workflow_id", CLType::U256),
-            Parameter::new("to_state", CLType::U8),
-            Parameter::new("actor_role", CLType::U64),
-            Parameter::new("comment_hash", CLType::ByteArray(32)),
-        ],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Called,
-    ).into());
+    #[test]
+    fn moved_ids_leave_their_old_bucket() {
+        let (one, two, three) = (U256::from(1), U256::from(2), U256::from(3));
+        let mut pending = vec![one, two, three];
+        let mut approved = vec![U256::from(9)];
+        
+        move_index_entry(&mut pending, &mut approved, two);
+        assert_eq!(pending, vec![one, three]);
+        assert_eq!(approved, vec![U256::from(9), two]);
+        
+        // New workflows come from no bucket
+        let mut drafts = Vec::new();
+        move_index_entry(&mut Vec::new(), &mut drafts, one);
+        assert_eq!(drafts, vec![one]);
+    }
     
-    // get_workflow_state
-    entry_points.add_entry_point(EntryPoint::new(
-        "get_workflow_state",
-        vec![
-            Parameter::new("workflow_id", CLType::U256),
-        ],
-        CLType::Any,
-        EntryPointAccess::Public,
-        EntryPointType::Called,
-    ).into());
+    #[test]
+    fn reassigned_workflows_move_between_creators() {
+        let (first, second) = (U256::from(1), U256::from(2));
+        let mut old_created = vec![first, second];
+        let mut new_created = vec![U256::from(5)];
+        
+        move_index_entry(&mut old_created, &mut new_created, first);
+        assert_eq!(old_created, vec![second]);
+        assert_eq!(new_created, vec![U256::from(5), first]);
+        
+        move_index_entry(&mut old_created, &mut new_created, second);
+        assert!(old_created.is_empty());
+        assert_eq!(new_created.iter().filter(|id| **id == first).count(), 1);
+    }
     
-    // get_workflow_history
-    entry_points.add_entry_point(EntryPoint::new(
-        "get_workflow_history",
-        vec![
-            Parameter::new("workflow_id", CLType::U256),
-        ],
-        CLType::Any,
-        EntryPointAccess::Public,
-        EntryPointType::Called,
-    ).into());
+    #[test]
+    fn reconcile_covers_custom_ids_mixed_with_auto_ids() {
+        // Auto IDs 1..=3, custom IDs 5 and 7, and a far-off derived ID
+        let derived = U256::from(u64::MAX);
+        let used = [1u64, 2, 3, 5, 7].map(U256::from);
+        let in_use = |id: U256| used.contains(&id) || id == derived;
+        let count = U256::from(3);
+        
+        let highest = highest_countable_in_use(U256::one(), MAX_PAGE_SIZE, count, in_use);
+        assert_eq!(highest, Some(U256::from(7)));
+        
+        // The next auto ID after reconciling skips nothing in use
+        assert_eq!(next_free_id(highest.unwrap(), in_use), Some(U256::from(8)));
+        
+        // Derived IDs stay outside the count
+        assert_eq!(highest_countable_in_use(derived, 1, count, in_use), None);
+        assert_eq!(highest_countable_in_use(U256::from(8), 10, count, in_use), None);
+    }
     
-    // get_workflow_count
-    entry_points.add_entry_point(EntryPoint::new(
-        "get_workflow_count",
-        vec![],
-        CLType::U256,
-        EntryPointAccess::Public,
-        EntryPointType::Called,
-    ).into());
+    #[test]
+    fn only_registered_categories_are_accepted() {
+        let registered = |id: u32| id == 3;
+        assert_eq!(check_category(Some(3), registered, || false), Ok(()));
+        let unregistered = check_category(Some(4), registered, || false);
+        assert_eq!(unregistered, Err(WorkflowError::InvalidArgument));
+        assert_eq!(check_category(None, registered, || false), Ok(()));
+    }
     
-    // register_compliance_proof - stores proof hash for approved workflows
-    entry_points.add_entry_point(EntryPoint::new(
-        "register_compliance_proof",
-        vec![
-            Parameter::new("workflow_id", CLType::U256),
-            Parameter::new("proof_hash", CLType::ByteArray(32)),
-        ],
-        CLType::Unit,
-        EntryPointAccess::Public,
-        EntryPointType::Called,
-    ).into());
+    #[test]
+    fn required_categories_must_be_supplied() {
+        let registered = |id: u32| id == 3;
+        assert_eq!(check_category(None, registered, || true), Err(WorkflowError::MissingArgument));
+        assert_eq!(check_category(Some(3), registered, || true), Ok(()));
+    }
     
-    // get_compliance_proof - retrieves proof hash for a workflow
-    entry_points.add_entry_point(EntryPoint::new(
-        "get_compliance_proof",
-        vec![
-            Parameter::new("workflow_id", CLType::U256),
-        ],
-        CLType::ByteArray(32),
-        EntryPointAccess::Public,
-        EntryPointType::Called,
-    ).into());
+    #[test]
+    fn external_refs_persist_through_serialization() {
+        let mut transitions = chain(2);
+        transitions[1].external_ref = [0xeeu8; 32];
+        let bytes = transitions.to_bytes().unwrap();
+        
+        let (decoded, remainder) = Vec::<TransitionRecord>::from_bytes(&bytes).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(decoded[0].external_ref, [0u8; 32]);
+        assert_eq!(decoded[1].external_ref, [0xeeu8; 32]);
+    }
     
-    // Install contract - Casper 2.0 new_contract has 5 args (message_topics)
-    let (contract_hash, _contract_version) = storage::new_contract(
-        entry_points,
-        Some(named_keys),
-        Some("workflow_contract_package".into()),
-        Some("workflow_contract_access".into()),
-        None, // message_topics - new in Casper 2.0
-    );
+    /// (workflow ID, record count) per returned history.
+    fn shape(histories: &[(U256, Vec<TransitionRecord>)]) -> Vec<(u32, usize)> {
+        histories.iter().map(|(id, records)| (id.as_u32(), records.len())).collect()
+    }
     
-    // Store contract hash for reference
-    runtime::put_key("workflow_contract", contract_hash.into());
+    #[test]
+    fn histories_batch_skips_missing_and_cuts_at_budget() {
+        let read = |id: U256| match id.as_u32() {
+            1 => Some(chain(3)),
+            2 => None,
+            3 => Some(chain(4)),
+            _ => Some(chain(2)),
+        };
+        let ids = vec![U256::from(1), U256::from(2), U256::from(3), U256::from(4)];
+        let (histories, truncated) = collect_histories(ids, 5, read);
+        assert!(truncated);
+        assert_eq!(shape(&histories), vec![(1, 3), (3, 2)]);
+        assert_eq!(histories[1].1[1].timestamp, chain(4)[1].timestamp);
+    }
+    
+    #[test]
+    fn histories_batch_flags_only_dropped_records() {
+        let read = |id: U256| match id.as_u32() {
+            1 => Some(chain(3)),
+            2 => Some(Vec::new()),
+            _ => Some(chain(1)),
+        };
+        let (histories, truncated) = collect_histories(vec![U256::from(1), U256::from(2)], 3, read);
+        assert!(!truncated);
+        assert_eq!(shape(&histories), vec![(1, 3), (2, 0)]);
+        
+        let (histories, truncated) = collect_histories(vec![U256::from(1), U256::from(3)], 3, read);
+        assert!(truncated);
+        assert_eq!(shape(&histories), vec![(1, 3)]);
+    }
+    
+    #[test]
+    fn assigned_roles_keep_the_admin_bit() {
+        let granted = assignable_mask(roles::ADMIN | roles::AUDITOR, roles::REQUESTER);
+        assert_eq!(granted, Ok(roles::AUDITOR));
+        let kept = assignable_mask(roles::REQUESTER, roles::ADMIN);
+        assert_eq!(kept, Ok(roles::ADMIN | roles::REQUESTER));
+        assert_eq!(assignable_mask(roles::SYSTEM_FLAG, 0), Err(WorkflowError::InvalidArgument));
+        assert_eq!(assignable_mask(roles::OVERRIDE_FLAG, 0), Err(WorkflowError::InvalidArgument));
+    }
 }